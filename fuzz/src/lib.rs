@@ -0,0 +1,16 @@
+use aoc_2023_rust::{fuzz_solution, Selector, PANICKING_DAYS};
+
+// The days live in the binary crate, so the fuzz targets build them from
+// its sources.
+#[allow(dead_code)]
+#[path = "../../src/solutions/mod.rs"]
+mod solutions;
+
+/// Runs a day on fuzzer input. Panics, which the fuzzer reports as a crash,
+/// unless the day's parser is still known to panic on bad input.
+pub fn fuzz_day(day: u8, data: &[u8]) {
+    let passed = fuzz_solution(|| solutions::create(day).unwrap(), data);
+    if !passed && !PANICKING_DAYS.contains(&(day as usize)) {
+        panic!("Day {day} panicked");
+    }
+}
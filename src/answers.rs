@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// Recorded answers for the input files of one directory.
+///
+/// The answers live next to the inputs in `answers.txt`, one
+/// `<input file> <part> <answer>` entry per line, e.g. `day01.txt 1 54081`.
+#[derive(Debug, Default)]
+pub struct AnswerStore {
+    path: PathBuf,
+    answers: BTreeMap<(String, u8), String>,
+}
+
+impl AnswerStore {
+    pub fn load<T: AsRef<Path>>(dir: T) -> Self {
+        let path = dir.as_ref().join(ANSWERS_FILE);
+        let mut answers = BTreeMap::new();
+
+        if let Ok(data) = read_to_string(&path) {
+            for line in data.lines().filter(|l| !l.trim().is_empty()) {
+                let mut fields = line.trim().splitn(3, ' ');
+                let (Some(file), Some(part), Some(answer)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    continue;
+                };
+                if let Ok(part) = part.parse::<u8>() {
+                    answers.insert((file.to_string(), part), answer.trim().to_string());
+                }
+            }
+        }
+
+        Self { path, answers }
+    }
+
+    /// Loads the store of the directory `input` is in.
+    pub fn for_input<T: AsRef<Path>>(input: T) -> Self {
+        Self::load(input.as_ref().parent().unwrap_or(Path::new(".")))
+    }
+
+    pub fn get(&self, file: &str, part: u8) -> Option<&str> {
+        self.answers
+            .get(&(file.to_string(), part))
+            .map(|a| a.as_str())
+    }
+
+    pub fn insert(&mut self, file: &str, part: u8, answer: &str) {
+        self.answers
+            .insert((file.to_string(), part), answer.to_string());
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let mut data = String::new();
+        for ((file, part), answer) in &self.answers {
+            data.push_str(&format!("{file} {part} {answer}\n"));
+        }
+        write(&self.path, data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answers_round_trip() {
+        let dir = std::env::temp_dir().join("aoc-2023-answers-round-trip");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut store = AnswerStore::load(&dir);
+        store.insert("day01.txt", 1, "54081");
+        store.insert("day01.txt", 2, "54649");
+        store.save().unwrap();
+
+        let store = AnswerStore::for_input(dir.join("day01.txt"));
+        assert_eq!(store.get("day01.txt", 1), Some("54081"));
+        assert_eq!(store.get("day01.txt", 2), Some("54649"));
+        assert_eq!(store.get("day02.txt", 1), None);
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use colored::*;
+
+use crate::{catch_panic, Runner};
+
+/// A property of the input that a day relies on without checking it while
+/// solving, e.g. that the ranges of a map never overlap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assumption {
+    pub name: &'static str,
+    /// Part of the input that breaks the assumption, `None` when it holds.
+    pub counter_example: Option<String>,
+}
+
+impl Assumption {
+    pub fn check(name: &'static str, counter_example: Option<String>) -> Self {
+        Self {
+            name,
+            counter_example,
+        }
+    }
+
+    pub fn holds(&self) -> bool {
+        self.counter_example.is_none()
+    }
+}
+
+impl Display for Assumption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.counter_example {
+            None => write!(f, "{} {}", "✓".green(), self.name),
+            Some(example) => write!(f, "{} {}: {example}", "✗".red(), self.name),
+        }
+    }
+}
+
+/// Parses `input` with a fresh solution and checks the assumptions of its
+/// day. `None` if the input cannot be parsed at all.
+pub fn verify_input<F>(new_solution: F, input: Option<&str>) -> Option<Vec<Assumption>>
+where
+    F: Fn() -> Box<dyn Runner>,
+{
+    catch_panic(|| {
+        let mut solution = new_solution();
+        solution.parse(input);
+        solution.assumptions()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counter_example() {
+        let holds = Assumption::check("sorted", None);
+        let broken = Assumption::check("sorted", Some("3 comes before 2".to_string()));
+        assert!(holds.holds() && !broken.holds());
+        let (holds, broken) = crate::without_colors(|| (holds.to_string(), broken.to_string()));
+        assert_eq!(holds, "✓ sorted");
+        assert_eq!(broken, "✗ sorted: 3 comes before 2");
+    }
+}
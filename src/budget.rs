@@ -0,0 +1,145 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use colored::*;
+
+use crate::Runner;
+
+/// Environment variable that turns the budget tests on. They are meant to be
+/// run with `cargo test --release`.
+pub const PERF_VAR: &str = "AOC_PERF";
+/// Environment variable with the factor a median may exceed its budget by.
+pub const TOLERANCE_VAR: &str = "AOC_PERF_TOLERANCE";
+/// Environment variable with the number of runs of each phase.
+pub const RUNS_VAR: &str = "AOC_PERF_RUNS";
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Time a day is expected to take on its real input in a release build, per
+/// phase: parsing, part 1 and part 2. Phases without a budget are not checked.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    pub phases: [Option<Duration>; 3],
+}
+
+impl Budget {
+    pub fn millis(parse: u64, part1: u64, part2: u64) -> Self {
+        Self {
+            phases: [parse, part1, part2].map(|ms| Some(Duration::from_millis(ms))),
+        }
+    }
+
+    /// Leaves a phase, e.g. a brute force that is known to be slow, unchecked.
+    pub fn without(mut self, phase: usize) -> Self {
+        self.phases[phase] = None;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerfSettings {
+    pub runs: usize,
+    pub tolerance: f64,
+}
+
+impl PerfSettings {
+    /// Settings of the budget tests, `None` unless they are turned on.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(PERF_VAR).ok().filter(|v| v != "0")?;
+        let var = |name: &str| std::env::var(name).ok();
+        Some(Self {
+            runs: var(RUNS_VAR).and_then(|r| r.parse().ok()).unwrap_or(5),
+            tolerance: var(TOLERANCE_VAR)
+                .and_then(|t| t.parse().ok())
+                .unwrap_or(1.5),
+        })
+    }
+}
+
+/// Median time of each phase over `runs` runs. Every run starts from a
+/// freshly parsed solution, as the parts may change its state.
+pub fn measure_phases<F>(
+    new_solution: F,
+    input: Option<&str>,
+    runs: usize,
+    budget: &Budget,
+) -> [Option<Duration>; 3]
+where
+    F: Fn() -> Box<dyn Runner>,
+{
+    let mut times: [Vec<Duration>; 3] = Default::default();
+    for _ in 0..runs.max(1) {
+        for (phase, limit) in budget.phases.iter().enumerate() {
+            if limit.is_none() {
+                continue;
+            }
+            let mut solution = new_solution();
+            let start = Instant::now();
+            solution.parse(input);
+            let parsed = start.elapsed();
+            let start = Instant::now();
+            match phase {
+                0 => Vec::new(),
+                1 => solution.part1(),
+                _ => solution.part2(),
+            };
+            times[phase].push(if phase == 0 { parsed } else { start.elapsed() });
+        }
+    }
+    times.map(|mut t| {
+        t.sort();
+        t.get(t.len() / 2).copied()
+    })
+}
+
+/// Phases of a day whose median exceeds the budget times `tolerance`, and a
+/// report of each checked phase with its budget against its median.
+pub fn check_budget(
+    day: usize,
+    budget: &Budget,
+    medians: &[Option<Duration>; 3],
+    tolerance: f64,
+) -> (Vec<String>, String) {
+    let mut over = Vec::new();
+    let mut report = String::new();
+    for (phase, (limit, median)) in budget.phases.iter().zip(medians).enumerate() {
+        let (Some(limit), Some(median)) = (limit, median) else {
+            continue;
+        };
+        let ok = median.as_secs_f64() <= limit.as_secs_f64() * tolerance;
+        let _ = writeln!(
+            report,
+            "Day {day:02} {:5}  budget {:>10}  median {:>10}  {}",
+            PHASES[phase],
+            format!("{limit:.2?}"),
+            format!("{median:.2?}"),
+            if ok { "✓".green() } else { "✗".red() },
+        );
+        if !ok {
+            over.push(format!("day {day:02} {}", PHASES[phase]));
+        }
+    }
+    (over, report)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn over_budget() {
+        let budget = Budget::millis(1, 10, 100).without(2);
+        let medians = [
+            Some(Duration::from_micros(1400)),
+            Some(Duration::from_millis(20)),
+            None,
+        ];
+        let (over, report) = crate::without_colors(|| check_budget(3, &budget, &medians, 1.5));
+        assert_eq!(over, vec!["day 03 part1"]);
+        assert_eq!(
+            report,
+            "Day 03 parse  budget     1.00ms  median     1.40ms  ✓\n\
+             Day 03 part1  budget    10.00ms  median    20.00ms  ✗\n"
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use std::fmt::Display;
+use std::fs::remove_file;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use colored::*;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::fuzzing::write_temp_input;
+use crate::{catch_panic, read_input, Runner, PANICKED};
+
+/// Default file the external programs are read from.
+pub const CROSSCHECK_CONFIG: &str = "crosscheck.json";
+
+/// External solution programs, e.g. solutions in another language.
+#[derive(Debug, Deserialize)]
+pub struct CrossCheckConfig {
+    pub programs: Vec<Program>,
+}
+
+impl CrossCheckConfig {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        for program in &config.programs {
+            program.pattern()?;
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputMode {
+    /// The input is written to the program's standard input.
+    #[default]
+    Stdin,
+    /// The path of the input is given as `{input}`, or as the last argument.
+    Path,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Program {
+    pub name: String,
+    /// The program and its arguments, where `{day}` is replaced with the
+    /// zero-padded day and `{input}` with the path of the input.
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub input: InputMode,
+    /// Regex matching an answer in the standard output. A `part` group gives
+    /// the part of the `answer` group, otherwise the matches are the answers
+    /// of part 1 and part 2 in order. One answer per line by default.
+    #[serde(default = "default_pattern")]
+    pub pattern: String,
+    /// Seconds the program may run on one input.
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+    /// Days the program solves, all days when empty.
+    #[serde(default)]
+    pub days: Vec<u8>,
+}
+
+fn default_pattern() -> String {
+    r"(?m)^\s*(?P<answer>\S+)\s*$".to_string()
+}
+
+fn default_timeout() -> u64 {
+    10
+}
+
+impl Program {
+    pub fn solves(&self, day: u8) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    fn pattern(&self) -> Result<Regex, String> {
+        let pattern =
+            Regex::new(&self.pattern).map_err(|e| format!("{}: bad pattern: {e}", self.name))?;
+        if pattern.capture_names().flatten().all(|n| n != "answer") {
+            return Err(format!("{}: pattern has no `answer` group", self.name));
+        }
+        Ok(pattern)
+    }
+
+    /// Runs the program on `input` and returns the answers found in its
+    /// output for part 1 and part 2.
+    pub fn answers(&self, day: u8, input: &Path) -> Result<[Option<String>; 2], String> {
+        let data = read_input(input).map_err(|e| format!("{}: {e}", input.display()))?;
+        // An encrypted input is handed to the program decrypted.
+        let temp = (self.input == InputMode::Path && !input.is_file())
+            .then(|| write_temp_input(data.as_bytes()));
+        let path = temp.as_deref().unwrap_or(input);
+        let output = self.run(day, path, data);
+        if let Some(temp) = temp {
+            let _ = remove_file(temp);
+        }
+        Ok(parse_answers(&self.pattern()?, &output?))
+    }
+
+    fn run(&self, day: u8, path: &Path, data: String) -> Result<String, String> {
+        let path = path.to_string_lossy();
+        let mut args: Vec<String> = self
+            .command
+            .iter()
+            .map(|a| {
+                a.replace("{day}", &format!("{day:02}"))
+                    .replace("{input}", &path)
+            })
+            .collect();
+        if self.input == InputMode::Path && !self.command.iter().any(|a| a.contains("{input}")) {
+            args.push(path.to_string());
+        }
+        let Some((program, args)) = args.split_first() else {
+            return Err(format!("{}: empty command", self.name));
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(match self.input {
+                InputMode::Stdin => Stdio::piped(),
+                InputMode::Path => Stdio::null(),
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("unable to start {program}: {e}"))?;
+
+        // The pipes are served on their own threads, so that a program that
+        // fills them cannot block it.
+        if let Some(mut stdin) = child.stdin.take() {
+            std::thread::spawn(move || stdin.write_all(data.as_bytes()));
+        }
+        let read_all = |mut pipe: Box<dyn Read + Send>| {
+            std::thread::spawn(move || {
+                let mut text = String::new();
+                let _ = pipe.read_to_string(&mut text);
+                text
+            })
+        };
+        let stdout = read_all(Box::new(child.stdout.take().unwrap()));
+        let stderr = read_all(Box::new(child.stderr.take().unwrap()));
+
+        let timeout = Duration::from_secs(self.timeout);
+        let start = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+                break status;
+            }
+            if start.elapsed() > timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("timed out after {timeout:?}"));
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        if !status.success() {
+            let stderr = stderr.join().unwrap_or_default();
+            return Err(format!("{status}: {}", stderr.trim()));
+        }
+        Ok(stdout.join().unwrap_or_default())
+    }
+}
+
+/// Answers of part 1 and part 2 matched by `pattern` in `stdout`.
+pub fn parse_answers(pattern: &Regex, stdout: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    for (i, captures) in pattern.captures_iter(stdout).enumerate() {
+        let part = match captures.name("part") {
+            Some(part) => part.as_str().parse().unwrap_or(0),
+            None => i + 1,
+        };
+        if let (1..=2, Some(answer)) = (part, captures.name("answer")) {
+            answers[part - 1].get_or_insert_with(|| answer.as_str().to_string());
+        }
+    }
+    answers
+}
+
+/// The answers of this crate and of an external program to a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartCheck {
+    pub part: u8,
+    pub ours: String,
+    pub theirs: Option<String>,
+}
+
+impl PartCheck {
+    pub fn agrees(&self) -> bool {
+        self.theirs.as_deref() == Some(self.ours.as_str())
+    }
+}
+
+impl Display for PartCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.theirs {
+            _ if self.agrees() => write!(f, "part {} {} {}", self.part, self.ours, "✓".green()),
+            Some(theirs) => write!(
+                f,
+                "part {} ours {}, theirs {theirs} {}",
+                self.part,
+                self.ours,
+                "✗".red()
+            ),
+            None => write!(
+                f,
+                "part {} ours {}, theirs missing {}",
+                self.part,
+                self.ours,
+                "✗".red()
+            ),
+        }
+    }
+}
+
+/// Compares the answers of a day on `input` with those of `program`.
+pub fn crosscheck<F>(
+    new_solution: F,
+    program: &Program,
+    input: &Path,
+) -> Result<[PartCheck; 2], String>
+where
+    F: Fn() -> Box<dyn Runner>,
+{
+    let day = new_solution().name().1 as u8;
+    let theirs = program.answers(day, input)?;
+    let pathname = input.to_string_lossy();
+    let ours = catch_panic(|| {
+        let mut solution = new_solution();
+        solution.parse(Some(&pathname));
+        [solution.part1().join("\n"), solution.part2().join("\n")]
+    })
+    .unwrap_or_else(|| [PANICKED.to_string(), PANICKED.to_string()]);
+    let [ours1, ours2] = ours;
+    let [theirs1, theirs2] = theirs;
+    Ok([
+        PartCheck {
+            part: 1,
+            ours: ours1,
+            theirs: theirs1,
+        },
+        PartCheck {
+            part: 2,
+            ours: ours2,
+            theirs: theirs2,
+        },
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use super::*;
+    use crate::output;
+
+    const SCRIPT: &str = "tests/fixtures/crosscheck.sh";
+
+    /// Counts the lines of the input in part 1 and its characters in part 2.
+    #[derive(Default)]
+    struct Counter {
+        input: String,
+    }
+
+    impl Runner for Counter {
+        fn name(&self) -> (usize, usize) {
+            (2023, 1)
+        }
+
+        fn parse(&mut self, input: Option<&str>) {
+            self.input = read_to_string(input.unwrap()).unwrap();
+        }
+
+        fn part1(&mut self) -> Vec<String> {
+            output(self.input.lines().count())
+        }
+
+        fn part2(&mut self) -> Vec<String> {
+            output(self.input.len())
+        }
+    }
+
+    fn counter() -> Box<dyn Runner> {
+        Box::<Counter>::default()
+    }
+
+    fn program(json: &str) -> Program {
+        CrossCheckConfig::from_json(&format!(r#"{{"programs": [{json}]}}"#))
+            .unwrap()
+            .programs
+            .remove(0)
+    }
+
+    #[test]
+    fn compares_with_script() {
+        let input = write_temp_input(b"a\nbb\nccc\n");
+        let stdin = program(&format!(
+            r#"{{"name": "sh", "command": ["sh", "{SCRIPT}", "stdin"],
+                "pattern": "Part (?P<part>\\d): (?P<answer>\\S+)"}}"#
+        ));
+        let checks = crosscheck(counter, &stdin, &input).unwrap();
+        assert!(checks.iter().all(PartCheck::agrees), "{checks:?}");
+
+        // The script counts bytes without the newlines when given a path.
+        let path = program(&format!(
+            r#"{{"name": "sh", "command": ["sh", "{SCRIPT}", "path"], "input": "path"}}"#
+        ));
+        let checks = crosscheck(counter, &path, &input).unwrap();
+        assert!(checks[0].agrees());
+        assert_eq!(checks[1].theirs.as_deref(), Some("6"));
+
+        let slow = program(&format!(
+            r#"{{"name": "sh", "command": ["sh", "{SCRIPT}", "sleep"], "timeout": 1}}"#
+        ));
+        assert_eq!(
+            crosscheck(counter, &slow, &input),
+            Err("timed out after 1s".to_string())
+        );
+        let _ = remove_file(input);
+    }
+
+    #[test]
+    fn answers_in_order_or_by_part() {
+        let pattern = Regex::new(&default_pattern()).unwrap();
+        assert_eq!(
+            parse_answers(&pattern, "42\n  abc \n7\n"),
+            [Some("42".to_string()), Some("abc".to_string())]
+        );
+        let pattern = Regex::new(r"p(?P<part>\d)=(?P<answer>\d+)").unwrap();
+        assert_eq!(
+            parse_answers(&pattern, "p2=5 p1=3"),
+            [Some("3".to_string()), Some("5".to_string())]
+        );
+        assert!(CrossCheckConfig::from_json(
+            r#"{"programs": [{"name": "x", "command": ["x"], "pattern": "\\d+"}]}"#
+        )
+        .is_err());
+    }
+}
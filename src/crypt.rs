@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::fs::{read, read_to_string, write};
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+/// Environment variable holding the key of the encrypted inputs.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+/// Environment variable pointing to a file holding the key, `.aoc-key` is
+/// used when neither is set.
+pub const KEY_FILE_VAR: &str = "AOC_INPUT_KEY_FILE";
+pub const KEY_FILE: &str = ".aoc-key";
+/// Set by CI services. A test whose real input cannot be read fails there
+/// instead of being skipped.
+pub const CI_VAR: &str = "CI";
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// Derived keys by passphrase and salt.
+type KeyCache = Mutex<HashMap<(String, Vec<u8>), [u8; 32]>>;
+
+pub fn input_key() -> Option<String> {
+    if let Ok(key) = std::env::var(KEY_VAR) {
+        return Some(key);
+    }
+    let file = std::env::var(KEY_FILE_VAR).unwrap_or(KEY_FILE.to_string());
+    read_to_string(file)
+        .ok()
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty())
+}
+
+/// The cipher of a passphrase, with its key stretched by Argon2id. Every
+/// input has a salt of its own, and the keys are cached as deriving one
+/// takes a while.
+fn cipher(key: &str, salt: &[u8]) -> XChaCha20Poly1305 {
+    static KEYS: OnceLock<KeyCache> = OnceLock::new();
+    let mut keys = KEYS.get_or_init(Default::default).lock().unwrap();
+    let derived = keys
+        .entry((key.to_string(), salt.to_vec()))
+        .or_insert_with(|| {
+            let mut derived = [0; 32];
+            Argon2::default()
+                .hash_password_into(key.as_bytes(), salt, &mut derived)
+                .unwrap();
+            derived
+        });
+    XChaCha20Poly1305::new_from_slice(derived.as_slice()).unwrap()
+}
+
+/// Encrypts `plaintext` as a random salt and nonce followed by the
+/// ciphertext.
+pub fn encrypt(plaintext: &[u8], key: &str) -> Vec<u8> {
+    let mut salt = [0; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let mut data = salt.to_vec();
+    data.extend(nonce);
+    data.extend(cipher(key, &salt).encrypt(&nonce, plaintext).unwrap());
+    data
+}
+
+pub fn decrypt(data: &[u8], key: &str) -> Result<Vec<u8>, String> {
+    if data.len() < SALT_LEN + NONCE_LEN {
+        return Err("encrypted input is truncated".to_string());
+    }
+    let (salt, data) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    cipher(key, salt)
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "wrong key or corrupt encrypted input".to_string())
+}
+
+/// `input/day01.txt` is stored encrypted as `input/day01.txt.enc`.
+pub fn encrypted_path<T: AsRef<Path>>(pathname: T) -> PathBuf {
+    let mut path = pathname.as_ref().as_os_str().to_owned();
+    path.push(".enc");
+    PathBuf::from(path)
+}
+
+pub fn input_exists<T: AsRef<Path>>(pathname: T) -> bool {
+    pathname.as_ref().is_file() || encrypted_path(pathname).is_file()
+}
+
+/// Reads an input file, or decrypts its encrypted copy when only that is in
+/// the tree.
+pub fn read_input<T: AsRef<Path>>(pathname: T) -> std::io::Result<String> {
+    let path = pathname.as_ref();
+    let encrypted = encrypted_path(path);
+    if path.is_file() || !encrypted.is_file() {
+        return read_to_string(path);
+    }
+
+    let key = input_key().ok_or_else(|| {
+        Error::new(
+            ErrorKind::NotFound,
+            format!("{} is encrypted and {KEY_VAR} is not set", path.display()),
+        )
+    })?;
+    let data = decrypt(&read(&encrypted)?, &key)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{}: {e}", path.display())))?;
+    String::from_utf8(data).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+/// Checks that a test can read `pathname`, telling why it is skipped if not.
+/// Panics instead under CI, so that a missing input or key does not pass
+/// the tests without running them.
+pub fn input_available<T: AsRef<Path>>(pathname: T) -> bool {
+    let path = pathname.as_ref();
+    if path.is_file() || (encrypted_path(path).is_file() && input_key().is_some()) {
+        return true;
+    }
+    let reason = format!("{} is missing, set {KEY_VAR} to decrypt it", path.display());
+    if std::env::var_os(CI_VAR).is_some() {
+        panic!("{reason}");
+    }
+    eprintln!("skipped: {reason}");
+    false
+}
+
+/// Writes the encrypted copy of a plaintext input next to it. An encrypted
+/// copy that is already up to date is kept, so that it does not show up as
+/// modified.
+pub fn encrypt_file<T: AsRef<Path>>(pathname: T, key: &str) -> std::io::Result<PathBuf> {
+    let encrypted = encrypted_path(&pathname);
+    let plaintext = read(pathname)?;
+    let current = read(&encrypted)
+        .ok()
+        .and_then(|data| decrypt(&data, key).ok());
+    if current.as_ref() != Some(&plaintext) {
+        write(&encrypted, encrypt(&plaintext, key))?;
+    }
+    Ok(encrypted)
+}
+
+/// Writes the plaintext of an encrypted input next to it.
+pub fn decrypt_file<T: AsRef<Path>>(pathname: T, key: &str) -> std::io::Result<PathBuf> {
+    let path = pathname.as_ref();
+    let data = decrypt(&read(encrypted_path(path))?, key)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{}: {e}", path.display())))?;
+    write(path, data)?;
+    Ok(path.to_path_buf())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encrypt_round_trip() {
+        let data = encrypt(b"467..114..", "secret");
+        assert_ne!(data, encrypt(b"467..114..", "secret"));
+        assert_eq!(decrypt(&data, "secret").unwrap(), b"467..114..");
+        assert!(decrypt(&data, "wrong").is_err());
+        assert!(decrypt(&data[..SALT_LEN + NONCE_LEN - 1], "secret").is_err());
+
+        let path = std::env::temp_dir().join(format!("aoc-crypt-{}.txt", std::process::id()));
+        write(&path, "467..114..").unwrap();
+        let encrypted = encrypt_file(&path, "secret").unwrap();
+        let data = read(&encrypted).unwrap();
+        encrypt_file(&path, "secret").unwrap();
+        assert_eq!(read(&encrypted).unwrap(), data);
+        write(&path, "617*......").unwrap();
+        encrypt_file(&path, "secret").unwrap();
+        assert_eq!(
+            decrypt(&read(&encrypted).unwrap(), "secret").unwrap(),
+            b"617*......"
+        );
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(encrypted).unwrap();
+    }
+}
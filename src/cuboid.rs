@@ -0,0 +1,176 @@
+use std::fmt::Display;
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+use crate::{Point, Point3};
+
+/// One of the planes spanned by two axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Plane {
+    XY,
+    XZ,
+    YZ,
+}
+
+impl Plane {
+    /// The point of `point` in the plane, dropping the third axis.
+    pub fn project<T>(self, point: Point3<T>) -> Point<T> {
+        match self {
+            Plane::XY => Point::new(point.x, point.y),
+            Plane::XZ => Point::new(point.x, point.z),
+            Plane::YZ => Point::new(point.y, point.z),
+        }
+    }
+}
+
+/// The box of points between `min` and `max`, both included, as the bricks
+/// `1,0,1~1,2,1` of the puzzles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Cuboid<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+impl<T: Display> Display for Cuboid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{},{}~{},{},{}",
+            self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z
+        )
+    }
+}
+
+impl<T: Copy + Ord + Add<T, Output = T> + Sub<T, Output = T>> Cuboid<T> {
+    /// The cuboid with `a` and `b` as opposite corners, in any order.
+    pub fn new(a: Point3<T>, b: Point3<T>) -> Self {
+        Self {
+            min: a.min(&b),
+            max: a.max(&b),
+        }
+    }
+
+    pub fn contains(&self, point: &Point3<T>) -> bool {
+        self.min.max(point) == *point && self.max.min(point) == *point
+    }
+
+    /// The points in both cuboids, `None` if they share none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let (min, max) = (self.min.max(&other.min), self.max.min(&other.max));
+        (min.max(&max) == max).then_some(Self { min, max })
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The cuboid moved by `offset`.
+    pub fn translate(&self, offset: Point3<T>) -> Self {
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// The corners of the rectangle the cuboid covers in `plane`, as the
+    /// footprint of a brick in the `XY` plane.
+    pub fn project(&self, plane: Plane) -> (Point<T>, Point<T>) {
+        (plane.project(self.min), plane.project(self.max))
+    }
+}
+
+impl<T> Cuboid<T>
+where
+    T: Copy + Ord + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T> + From<u8>,
+{
+    /// Points in the cuboid.
+    pub fn volume(&self) -> T {
+        let size = |min: T, max: T| max - min + T::from(1);
+        size(self.min.x, self.max.x) * size(self.min.y, self.max.y) * size(self.min.z, self.max.z)
+    }
+}
+
+/// Parses two corners separated by `~`, as in `1,0,1~1,2,1`.
+impl<T> FromStr for Cuboid<T>
+where
+    T: FromStr + Copy + Ord + Add<T, Output = T> + Sub<T, Output = T>,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (a, b) = s
+            .trim()
+            .split_once('~')
+            .ok_or_else(|| format!("no '~' in cuboid '{s}'"))?;
+        Ok(Self::new(a.parse()?, b.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cuboid(s: &str) -> Cuboid<i32> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let brick = cuboid("2,2,2~0,2,2");
+        assert_eq!(brick.min, Point3::new(0, 2, 2));
+        assert_eq!(brick.max, Point3::new(2, 2, 2));
+        assert_eq!(brick.to_string(), "0,2,2~2,2,2");
+        assert!("1,0,1".parse::<Cuboid<i32>>().is_err());
+        assert!("1,0~1,2,1".parse::<Cuboid<i32>>().is_err());
+    }
+
+    #[test]
+    fn volume_and_contains() {
+        let brick = cuboid("1,0,1~1,2,1");
+        assert_eq!(brick.volume(), 3);
+        assert_eq!(cuboid("0,0,0~0,0,0").volume(), 1);
+        assert_eq!(cuboid("0,0,0~9,9,9").volume(), 1000);
+        assert!(brick.contains(&Point3::new(1, 2, 1)));
+        assert!(!brick.contains(&Point3::new(1, 3, 1)));
+        assert!(!brick.contains(&Point3::new(0, 1, 1)));
+        assert_eq!("1,1,1~2,2,2".parse::<Cuboid<u64>>().unwrap().volume(), 8);
+    }
+
+    #[test]
+    fn intersection() {
+        let a = cuboid("0,0,0~4,4,4");
+        assert_eq!(
+            a.intersection(&cuboid("3,-1,2~8,1,2")),
+            Some(cuboid("3,0,2~4,1,2"))
+        );
+        // Touching faces share the points on them.
+        assert_eq!(
+            a.intersection(&cuboid("4,4,4~5,5,5")),
+            Some(cuboid("4,4,4~4,4,4"))
+        );
+        assert_eq!(a.intersection(&cuboid("5,0,0~6,4,4")), None);
+        assert!(a.overlaps(&cuboid("1,1,1~2,2,2")));
+        assert!(!a.overlaps(&cuboid("0,0,5~4,4,5")));
+        assert_eq!(a.intersection(&a), Some(a));
+    }
+
+    #[test]
+    fn translate_and_project() {
+        let brick = cuboid("0,1,6~2,1,6");
+        let fallen = brick.translate(Point3::new(0, 0, -5));
+        assert_eq!(fallen, cuboid("0,1,1~2,1,1"));
+        assert_eq!(fallen.volume(), brick.volume());
+        assert_eq!(
+            brick.project(Plane::XY),
+            (Point::new(0, 1), Point::new(2, 1))
+        );
+        assert_eq!(
+            brick.project(Plane::XZ),
+            (Point::new(0, 6), Point::new(2, 6))
+        );
+        assert_eq!(
+            brick.project(Plane::YZ),
+            (Point::new(1, 6), Point::new(1, 6))
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use crate::{catch_panic, Runner};
+
+/// Answer recorded for an implementation that panicked.
+pub const PANICKED: &str = "panicked";
+
+/// Another implementation of a part of a day, e.g. a faster rewrite that
+/// should give the same answers as the day's own `part1` or `part2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alternative {
+    pub part: u8,
+    pub name: &'static str,
+}
+
+impl Alternative {
+    pub fn new(part: u8, name: &'static str) -> Self {
+        Self { part, name }
+    }
+}
+
+/// The first input where the implementations of a part disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub input: PathBuf,
+    pub part: u8,
+    /// Answer of each implementation, the day's own part first.
+    pub answers: Vec<(String, String)>,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Part {} differs on {}", self.part, self.input.display())?;
+        for (name, answer) in &self.answers {
+            writeln!(f, "\t{name:12} {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+fn run_part<F>(
+    new_solution: &F,
+    input: &Path,
+    part: u8,
+    alternative: Option<&Alternative>,
+) -> String
+where
+    F: Fn() -> Box<dyn Runner>,
+{
+    let input = input.to_string_lossy();
+    catch_panic(|| {
+        let mut solution = new_solution();
+        solution.parse(Some(&input));
+        let output = match (alternative, part) {
+            (Some(alternative), _) => solution.run_alternative(alternative),
+            (None, 1) => solution.part1(),
+            (None, _) => solution.part2(),
+        };
+        output.join("\n")
+    })
+    .unwrap_or(PANICKED.to_string())
+}
+
+/// Runs every alternative of a day against the day's own parts on each of
+/// `inputs`, with a fresh solution for each run, and returns the first input
+/// where the answers differ.
+pub fn find_mismatch<F>(new_solution: F, inputs: &[PathBuf]) -> Option<Mismatch>
+where
+    F: Fn() -> Box<dyn Runner>,
+{
+    let alternatives = new_solution().alternatives();
+
+    for input in inputs {
+        for part in [1, 2] {
+            let candidates: Vec<&Alternative> =
+                alternatives.iter().filter(|a| a.part == part).collect();
+            if candidates.is_empty() {
+                continue;
+            }
+
+            let mut answers = vec![(
+                format!("part{part}"),
+                run_part(&new_solution, input, part, None),
+            )];
+            for alternative in candidates {
+                answers.push((
+                    alternative.name.to_string(),
+                    run_part(&new_solution, input, part, Some(alternative)),
+                ));
+            }
+            if answers.iter().any(|(_, a)| *a != answers[0].1) {
+                return Some(Mismatch {
+                    input: input.clone(),
+                    part,
+                    answers,
+                });
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,294 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::Point;
+
+/// One of the four compass directions, with north up and `y` growing south
+/// as in the puzzle inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn all() -> [Direction; 4] {
+        Self::ALL
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self.index() + 2) % 4]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::North | Direction::South)
+    }
+
+    /// The step one tile towards the direction.
+    pub fn delta<T: From<i8>>(self) -> Point<T> {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        };
+        Point::new(x.into(), y.into())
+    }
+
+    /// The direction a beam travelling this way leaves a `/` or `\` mirror
+    /// in, `None` for any other character.
+    pub fn reflect(self, mirror: char) -> Option<Self> {
+        match mirror {
+            '/' if self.is_vertical() => Some(self.turn_right()),
+            '/' => Some(self.turn_left()),
+            '\\' if self.is_vertical() => Some(self.turn_left()),
+            '\\' => Some(self.turn_right()),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `U`, `D`, `L` and `R`, `N`, `S`, `E` and `W`, or an arrow such as
+/// `^` or `→`.
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "N" | "U" | "^" | "↑" => Ok(Direction::North),
+            "E" | "R" | ">" | "→" => Ok(Direction::East),
+            "S" | "D" | "v" | "↓" => Ok(Direction::South),
+            "W" | "L" | "<" | "←" => Ok(Direction::West),
+            other => Err(format!("unknown direction '{other}'")),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        c.to_string().parse()
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::North => write!(f, "N"),
+            Direction::East => write!(f, "E"),
+            Direction::South => write!(f, "S"),
+            Direction::West => write!(f, "W"),
+        }
+    }
+}
+
+/// One of the eight compass directions, including the diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    /// Clockwise from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    pub fn all() -> [Dir8; 8] {
+        Self::ALL
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// An eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /// An eighth of a turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// The step one tile towards the direction.
+    pub fn delta<T: From<i8>>(self) -> Point<T> {
+        let (x, y): (i8, i8) = match self {
+            Dir8::North => (0, -1),
+            Dir8::NorthEast => (1, -1),
+            Dir8::East => (1, 0),
+            Dir8::SouthEast => (1, 1),
+            Dir8::South => (0, 1),
+            Dir8::SouthWest => (-1, 1),
+            Dir8::West => (-1, 0),
+            Dir8::NorthWest => (-1, -1),
+        };
+        Point::new(x.into(), y.into())
+    }
+
+    /// The direction a beam travelling this way leaves a `/` or `\` mirror
+    /// in, `None` for any other character.
+    pub fn reflect(self, mirror: char) -> Option<Self> {
+        // A mirror swaps the axes of the step, negated for `/` as `y` grows
+        // south.
+        let Point { x, y } = self.delta::<i8>();
+        let (x, y) = match mirror {
+            '/' => (-y, -x),
+            '\\' => (y, x),
+            _ => return None,
+        };
+        Self::ALL
+            .into_iter()
+            .find(|d| d.delta::<i8>() == Point::new(x, y))
+    }
+}
+
+impl From<Direction> for Dir8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction.index() * 2]
+    }
+}
+
+/// Parses the directions of `Direction` and `NE`, `SE`, `SW` and `NW`, or
+/// a diagonal arrow such as `↗`.
+impl FromStr for Dir8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "NE" | "↗" => Ok(Dir8::NorthEast),
+            "SE" | "↘" => Ok(Dir8::SouthEast),
+            "SW" | "↙" => Ok(Dir8::SouthWest),
+            "NW" | "↖" => Ok(Dir8::NorthWest),
+            other => other.parse::<Direction>().map(Dir8::from),
+        }
+    }
+}
+
+impl Display for Dir8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Dir8::NorthEast => write!(f, "NE"),
+            Dir8::SouthEast => write!(f, "SE"),
+            Dir8::SouthWest => write!(f, "SW"),
+            Dir8::NorthWest => write!(f, "NW"),
+            other => write!(f, "{}", Direction::ALL[other.index() / 2]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        use Direction::*;
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(West.turn_right(), North);
+        assert_eq!(South.opposite(), North);
+        for d in Direction::all() {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(d.delta::<i32>() + d.opposite().delta(), Point::new(0, 0));
+        }
+        assert_eq!(North.delta(), Point::new(0, -1));
+        assert_eq!(East.delta::<i16>(), Point::new(1, 0));
+
+        assert_eq!(Dir8::North.turn_right(), Dir8::NorthEast);
+        assert_eq!(Dir8::North.turn_left(), Dir8::NorthWest);
+        assert_eq!(Dir8::SouthWest.opposite(), Dir8::NorthEast);
+        assert_eq!(Dir8::SouthWest.delta(), Point::new(-1, 1));
+        assert!(Dir8::all().iter().filter(|d| d.is_diagonal()).count() == 4);
+    }
+
+    #[test]
+    fn mirrors() {
+        use Direction::*;
+        // A beam moving east hits `/` and goes up, and `\` and goes down.
+        assert_eq!(East.reflect('/'), Some(North));
+        assert_eq!(East.reflect('\\'), Some(South));
+        assert_eq!(North.reflect('/'), Some(East));
+        assert_eq!(North.reflect('\\'), Some(West));
+        assert_eq!(South.reflect('/'), Some(West));
+        assert_eq!(West.reflect('\\'), Some(North));
+        assert_eq!(West.reflect('|'), None);
+        for d in Direction::all() {
+            for mirror in ['/', '\\'] {
+                let reflected = d.reflect(mirror).unwrap();
+                assert_eq!(reflected.opposite().reflect(mirror), Some(d.opposite()));
+                assert_eq!(Dir8::from(d).reflect(mirror), Some(Dir8::from(reflected)));
+            }
+        }
+        assert_eq!(Dir8::NorthEast.reflect('/'), Some(Dir8::NorthEast));
+        assert_eq!(Dir8::NorthEast.reflect('\\'), Some(Dir8::SouthWest));
+    }
+
+    #[test]
+    fn parsing() {
+        for (text, direction) in [
+            ("U", Direction::North),
+            ("R", Direction::East),
+            ("S", Direction::South),
+            ("<", Direction::West),
+            ("v", Direction::South),
+            ("↑", Direction::North),
+        ] {
+            assert_eq!(text.parse(), Ok(direction));
+        }
+        assert_eq!(Direction::try_from('L'), Ok(Direction::West));
+        assert!("X".parse::<Direction>().is_err());
+        assert_eq!("NW".parse(), Ok(Dir8::NorthWest));
+        assert_eq!("E".parse(), Ok(Dir8::East));
+        for d in Dir8::all() {
+            assert_eq!(d.to_string().parse(), Ok(d));
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::fs::{read_dir, remove_file, write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{catch_panic, Runner};
+
+/// Directory of the inputs that crashed a day under the fuzzer, kept as
+/// regression inputs named `dayNN-<name>`.
+pub const REGRESSIONS_DIR: &str = "fuzz/regressions";
+
+/// Days whose parser still panics on bad input. Their fuzz targets and
+/// regression inputs may panic; any other day fails on a panic, so drop a day
+/// from the list once its parser reports bad input instead.
+pub const PANICKING_DAYS: &[usize] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+/// Parts that may run without end on arbitrary input, e.g. a walk that never
+/// reaches `ZZZ`, or a count over every millisecond of a race. They are not fuzzed.
+pub const UNBOUNDED_PARTS: &[(usize, u8)] = &[(5, 2), (6, 1), (6, 2), (8, 1), (8, 2)];
+
+/// Longer inputs are cut off, so that a single run stays quick.
+pub const MAX_FUZZ_INPUT: usize = 4096;
+
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes `data` to a temporary file of its own, for the parsers that only
+/// read inputs from a path.
+pub(crate) fn write_temp_input(data: &[u8]) -> PathBuf {
+    let run = RUNS.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!("aoc-input-{}-{run}.txt", std::process::id()));
+    write(&path, data).unwrap();
+    path
+}
+
+/// Feeds `data` to the parser and the bounded parts of a fresh solution.
+/// Returns `false` if any of them panicked.
+pub fn fuzz_solution<F>(new_solution: F, data: &[u8]) -> bool
+where
+    F: Fn() -> Box<dyn Runner>,
+{
+    let data = &data[..data.len().min(MAX_FUZZ_INPUT)];
+    let path = write_temp_input(data);
+    let pathname = path.to_string_lossy().to_string();
+
+    let mut solution = new_solution();
+    let day = solution.name().1;
+    let passed = catch_panic(|| {
+        solution.parse(Some(&pathname));
+        if !UNBOUNDED_PARTS.contains(&(day, 1)) {
+            solution.part1();
+        }
+        if !UNBOUNDED_PARTS.contains(&(day, 2)) {
+            solution.part2();
+        }
+    })
+    .is_some();
+    let _ = remove_file(&path);
+    passed
+}
+
+/// Regression inputs of `day` under `dir`.
+pub fn regression_inputs<T: AsRef<Path>>(dir: T, day: usize) -> Vec<PathBuf> {
+    let prefix = format!("day{day:02}-");
+    let mut inputs: Vec<PathBuf> = read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with(&prefix))
+        })
+        .collect();
+    inputs.sort();
+    inputs
+}
@@ -0,0 +1,109 @@
+use std::fs::{create_dir_all, write};
+use std::path::{Path, PathBuf};
+
+use crate::Runner;
+
+/// Seeded pseudo random numbers (SplitMix64), so that a generated input can
+/// be reproduced from its seed alone.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..high`.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low < high, "empty range {low}..{high}");
+        low + self.next_u64() % (high - low)
+    }
+
+    /// An index below `n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        self.range(0, n as u64) as usize
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// `count` distinct numbers from `low..high`, in random order.
+    pub fn distinct(&mut self, count: usize, low: u64, high: u64) -> Vec<u64> {
+        if high - low > 4 * count as u64 {
+            let mut numbers = Vec::new();
+            while numbers.len() < count {
+                let n = self.range(low, high);
+                if !numbers.contains(&n) {
+                    numbers.push(n);
+                }
+            }
+            return numbers;
+        }
+        let mut numbers: Vec<u64> = (low..high).collect();
+        self.shuffle(&mut numbers);
+        numbers.truncate(count);
+        numbers
+    }
+}
+
+/// Writes the generated inputs of a day for each of `seeds` into `dir` as
+/// `dayNN-gen-<seed>-<size>.txt`. Returns the written files.
+pub fn write_generated<T: AsRef<Path>>(
+    solution: &dyn Runner,
+    seeds: std::ops::Range<u64>,
+    size: usize,
+    dir: T,
+) -> std::io::Result<Vec<PathBuf>> {
+    create_dir_all(&dir)?;
+    let day = solution.name().1;
+    let mut written = Vec::new();
+    for seed in seeds {
+        let Some(input) = solution.generate(&mut Rng::new(seed), size) else {
+            break;
+        };
+        let path = dir
+            .as_ref()
+            .join(format!("day{day:02}-gen-{seed}-{size}.txt"));
+        write(&path, input)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seeded_numbers() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        let numbers: Vec<u64> = (0..5).map(|_| a.range(10, 20)).collect();
+        assert_eq!(numbers, (0..5).map(|_| b.range(10, 20)).collect::<Vec<_>>());
+        assert!(numbers.iter().all(|n| (10..20).contains(n)));
+
+        let mut distinct = a.distinct(10, 0, 10);
+        distinct.sort();
+        assert_eq!(distinct, (0..10).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,433 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::{extended_gcd, Direction, Point};
+
+/// An exact fraction, always in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "{numerator}/0");
+        let (g, _, _) = extended_gcd(numerator, denominator);
+        let sign = denominator.signum();
+        Self {
+            numerator: sign * numerator / g,
+            denominator: sign * denominator / g,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self::new(value as i128, 1)
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            _ => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.denominator + other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.denominator - other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.denominator,
+            self.denominator * other.numerator,
+        )
+    }
+}
+
+/// `a.x * b.y - a.y * b.x`, positive when `b` turns counterclockwise from `a`
+/// with `y` growing north.
+fn cross(a: Point<i64>, b: Point<i64>) -> i128 {
+    a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128
+}
+
+/// A closed polygon through its vertices in order, the last joined back to
+/// the first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Polygon {
+    pub vertices: Vec<Point<i64>>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point<i64>>) -> Self {
+        Self { vertices }
+    }
+
+    /// The polygon traced by walking each length in its direction from the
+    /// origin, as the dig plan `R 6`, `D 5`, ... of a lagoon.
+    pub fn from_walk(steps: impl IntoIterator<Item = (Direction, i64)>) -> Self {
+        let mut position = Point::new(0, 0);
+        let mut vertices = Vec::new();
+        for (direction, length) in steps {
+            vertices.push(position);
+            position += direction.delta::<i64>() * length;
+        }
+        Self { vertices }
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the area, by the shoelace formula, so that it is an integer.
+    /// Positive when the vertices run counterclockwise with `y` growing north.
+    pub fn signed_double_area(&self) -> i128 {
+        self.edges().map(|(a, b)| cross(a, b)).sum()
+    }
+
+    pub fn double_area(&self) -> i128 {
+        self.signed_double_area().abs()
+    }
+
+    pub fn area(&self) -> Rational {
+        Rational::new(self.double_area(), 2)
+    }
+
+    /// Lattice points on the edges.
+    pub fn boundary_points(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| extended_gcd((b.x - a.x) as i128, (b.y - a.y) as i128).0)
+            .sum()
+    }
+
+    /// Lattice points strictly inside, by Pick's theorem: the area is the
+    /// interior points plus half the boundary points, minus 1.
+    pub fn interior_points(&self) -> i128 {
+        (self.double_area() - self.boundary_points() + 2) / 2
+    }
+
+    pub fn on_boundary(&self, point: &Point<i64>) -> bool {
+        self.edges().any(|(a, b)| {
+            cross(b - a, *point - a) == 0
+                && a.min(&b).min(point) == a.min(&b)
+                && a.max(&b).max(point) == a.max(&b)
+        })
+    }
+
+    /// Whether `point` is strictly inside, by counting the edges a ray from
+    /// it crosses. Points on the boundary are not inside.
+    pub fn contains_ray_cast(&self, point: &Point<i64>) -> bool {
+        if self.on_boundary(point) {
+            return false;
+        }
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            // Edges count when they span the ray's y, with the lower end
+            // included, so that a vertex on the ray counts once.
+            if (a.y > point.y) != (b.y > point.y) {
+                // The edge crosses y to the right of the point.
+                let side = cross(b - a, *point - a);
+                if (side > 0) == (b.y > a.y) {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// How many times the boundary winds counterclockwise around `point`,
+    /// with `y` growing north, 0 on the boundary.
+    pub fn winding_number(&self, point: &Point<i64>) -> i64 {
+        if self.on_boundary(point) {
+            return 0;
+        }
+        let mut winding = 0;
+        for (a, b) in self.edges() {
+            let side = cross(b - a, *point - a);
+            if a.y <= point.y && b.y > point.y && side > 0 {
+                winding += 1;
+            } else if a.y > point.y && b.y <= point.y && side < 0 {
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    /// Whether `point` is strictly inside, by the winding number.
+    pub fn contains(&self, point: &Point<i64>) -> bool {
+        self.winding_number(point) != 0
+    }
+}
+
+/// The parameters `(t, u)` where `p + t * r` meets `q + u * s`, `None` for
+/// parallel lines.
+fn line_parameters(
+    p: Point<i64>,
+    r: Point<i64>,
+    q: Point<i64>,
+    s: Point<i64>,
+) -> Option<(Rational, Rational)> {
+    let denominator = cross(r, s);
+    if denominator == 0 {
+        return None;
+    }
+    Some((
+        Rational::new(cross(q - p, s), denominator),
+        Rational::new(cross(q - p, r), denominator),
+    ))
+}
+
+fn at(origin: Point<i64>, direction: Point<i64>, t: Rational) -> Point<Rational> {
+    Point::new(
+        Rational::from(origin.x) + Rational::from(direction.x) * t,
+        Rational::from(origin.y) + Rational::from(direction.y) * t,
+    )
+}
+
+/// The points from `start` to `end`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment {
+    pub start: Point<i64>,
+    pub end: Point<i64>,
+}
+
+impl Segment {
+    pub fn new(start: Point<i64>, end: Point<i64>) -> Self {
+        Self { start, end }
+    }
+
+    /// The one point where the segments cross. Parallel segments have none,
+    /// even when they overlap.
+    pub fn intersection(&self, other: &Segment) -> Option<Point<Rational>> {
+        let (r, s) = (self.end - self.start, other.end - other.start);
+        let (t, u) = line_parameters(self.start, r, other.start, s)?;
+        let within = |v: Rational| Rational::from(0) <= v && v <= Rational::from(1);
+        (within(t) && within(u)).then(|| at(self.start, r, t))
+    }
+}
+
+/// The points from `origin` onwards in `direction`, as the path of a
+/// hailstone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ray {
+    pub origin: Point<i64>,
+    pub direction: Point<i64>,
+}
+
+impl Ray {
+    pub fn new(origin: Point<i64>, direction: Point<i64>) -> Self {
+        Self { origin, direction }
+    }
+
+    /// The one point where the rays cross. Parallel rays have none, even
+    /// when they overlap.
+    pub fn intersection(&self, other: &Ray) -> Option<Point<Rational>> {
+        let (t, u) = line_parameters(self.origin, self.direction, other.origin, other.direction)?;
+        let zero = Rational::from(0);
+        (t >= zero && u >= zero).then(|| at(self.origin, self.direction, t))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn square(side: i64) -> Polygon {
+        Polygon::new(vec![
+            Point::new(0, 0),
+            Point::new(side, 0),
+            Point::new(side, side),
+            Point::new(0, side),
+        ])
+    }
+
+    #[test]
+    fn rationals() {
+        let half = Rational::new(2, 4);
+        assert_eq!((half.numerator(), half.denominator()), (1, 2));
+        assert_eq!(Rational::new(3, -6), Rational::new(-1, 2));
+        assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+        assert_eq!(half - Rational::from(1), Rational::new(-1, 2));
+        assert_eq!(half * Rational::new(4, 3), Rational::new(2, 3));
+        assert_eq!(half / Rational::new(1, 4), Rational::from(2));
+        assert!(Rational::new(-1, 2) < Rational::new(1, 3));
+        assert!(Rational::new(6, 3).is_integer());
+        assert_eq!(Rational::new(7, 2).to_f64(), 3.5);
+        assert_eq!(Rational::new(-7, 2).to_string(), "-7/2");
+        assert_eq!(Rational::new(0, -5).to_string(), "0");
+    }
+
+    #[test]
+    fn areas_and_lattice_points() {
+        let square = square(4);
+        assert_eq!(square.area(), Rational::from(16));
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+
+        let triangle = Polygon::new(vec![Point::new(0, 0), Point::new(3, 0), Point::new(0, 3)]);
+        assert_eq!(triangle.area(), Rational::new(9, 2));
+        assert_eq!(triangle.boundary_points(), 9);
+        assert_eq!(triangle.interior_points(), 1);
+        // Counterclockwise with y north, clockwise reversed.
+        assert_eq!(triangle.signed_double_area(), 9);
+        let mut reversed = triangle.clone();
+        reversed.vertices.reverse();
+        assert_eq!(reversed.signed_double_area(), -9);
+
+        // The lagoon of the 2023 day 18 example: 62 cubic meters.
+        let plan = "R 6,D 5,L 2,D 2,R 2,D 2,L 5,U 2,L 1,U 2,R 2,U 3,L 2,U 2";
+        let lagoon = Polygon::from_walk(plan.split(',').map(|step| {
+            let (direction, length) = step.split_once(' ').unwrap();
+            (direction.parse().unwrap(), length.parse().unwrap())
+        }));
+        assert_eq!(lagoon.vertices.len(), 14);
+        assert_eq!(lagoon.boundary_points(), 38);
+        assert_eq!(lagoon.interior_points() + lagoon.boundary_points(), 62);
+    }
+
+    #[test]
+    fn point_in_polygon() {
+        // A U shape open to the north, with y growing south.
+        let u = Polygon::new(vec![
+            Point::new(0, 0),
+            Point::new(2, 0),
+            Point::new(2, 4),
+            Point::new(4, 4),
+            Point::new(4, 0),
+            Point::new(6, 0),
+            Point::new(6, 6),
+            Point::new(0, 6),
+        ]);
+        for (point, inside) in [
+            (Point::new(1, 1), true),
+            (Point::new(3, 1), false),
+            (Point::new(3, 5), true),
+            (Point::new(5, 3), true),
+            (Point::new(7, 3), false),
+            (Point::new(-1, 0), false),
+            // On the boundary, and level with vertices.
+            (Point::new(2, 2), false),
+            (Point::new(3, 4), false),
+            (Point::new(1, 4), true),
+            (Point::new(-1, 4), false),
+        ] {
+            assert_eq!(u.contains_ray_cast(&point), inside, "{point}");
+            assert_eq!(u.contains(&point), inside, "{point}");
+        }
+        assert!(u.on_boundary(&Point::new(6, 6)));
+        assert_eq!(u.winding_number(&Point::new(1, 1)).abs(), 1);
+
+        // Interior points by Pick's theorem agree with counting them.
+        let counted = (-1..8)
+            .flat_map(|x| (-1..8).map(move |y| Point::new(x, y)))
+            .filter(|p| u.contains(p))
+            .count();
+        assert_eq!(counted as i128, u.interior_points());
+
+        // A pentagram winds twice around its center.
+        let star = Polygon::new(vec![
+            Point::new(0, 10),
+            Point::new(6, -8),
+            Point::new(-10, 3),
+            Point::new(10, 3),
+            Point::new(-6, -8),
+        ]);
+        assert_eq!(star.winding_number(&Point::new(0, 0)).abs(), 2);
+        assert!(!star.contains_ray_cast(&Point::new(0, 0)));
+        assert!(star.contains_ray_cast(&Point::new(0, 7)));
+    }
+
+    #[test]
+    fn intersections() {
+        let a = Segment::new(Point::new(0, 0), Point::new(4, 4));
+        let b = Segment::new(Point::new(0, 3), Point::new(3, 0));
+        let half = Rational::new(3, 2);
+        assert_eq!(a.intersection(&b), Some(Point::new(half, half)));
+        let short = Segment::new(Point::new(0, 3), Point::new(1, 2));
+        assert_eq!(a.intersection(&short), None);
+        let touching = Segment::new(Point::new(4, 4), Point::new(8, 0));
+        assert_eq!(
+            a.intersection(&touching),
+            Some(Point::new(Rational::from(4), Rational::from(4)))
+        );
+        let parallel = Segment::new(Point::new(1, 1), Point::new(5, 5));
+        assert_eq!(a.intersection(&parallel), None);
+
+        // Hailstones A and B of the 2023 day 24 example cross in the future
+        // at x=14.333, y=15.333, while A and E crossed in A's past.
+        let hailstone_a = Ray::new(Point::new(19, 13), Point::new(-2, 1));
+        let hailstone_b = Ray::new(Point::new(18, 19), Point::new(-1, -1));
+        let hailstone_e = Ray::new(Point::new(20, 19), Point::new(1, -5));
+        assert_eq!(
+            hailstone_a.intersection(&hailstone_b),
+            Some(Point::new(Rational::new(43, 3), Rational::new(46, 3)))
+        );
+        assert_eq!(hailstone_a.intersection(&hailstone_e), None);
+    }
+}
@@ -0,0 +1,628 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::Point;
+
+/// A rectangular map of tiles, such as a puzzle input of one character per
+/// tile, indexed by `Point`s with `x` growing east and `y` growing south.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self {
+            cells: Vec::new(),
+            width: 0,
+            height: 0,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Panics if the rows are not all as wide.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "rows of a grid differ in width"
+        );
+        Self {
+            height: rows.len(),
+            width,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Parses one tile per character and one row per line, skipping blank
+    /// lines. Panics if the rows are not all as wide.
+    pub fn parse(text: &str, tile: impl FnMut(char) -> T) -> Self {
+        let mut tile = tile;
+        Self::from_rows(
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.chars().map(&mut tile).collect())
+                .collect(),
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point<i32>) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    fn offset(&self, point: Point<i32>) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    /// The tile at `point`, `None` outside the grid.
+    pub fn get(&self, point: Point<i32>) -> Option<&T> {
+        self.offset(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point<i32>) -> Option<&mut T> {
+        self.offset(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Sets the tile at `point`. Panics outside the grid.
+    pub fn set(&mut self, point: Point<i32>, tile: T) {
+        self[point] = tile;
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point<i32>> {
+        let width = self.width;
+        (0..self.width * self.height)
+            .map(move |i| Point::new((i % width) as i32, (i / width) as i32))
+    }
+
+    /// Every tile with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i32>, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The first point, row by row, whose tile matches.
+    pub fn find(&self, mut matches: impl FnMut(&T) -> bool) -> Option<Point<i32>> {
+        self.iter().find(|(_, t)| matches(t)).map(|(p, _)| p)
+    }
+
+    /// Every point, row by row, whose tile matches.
+    pub fn find_all<'a>(
+        &'a self,
+        mut matches: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point<i32>> + 'a {
+        self.iter().filter(move |(_, t)| matches(t)).map(|(p, _)| p)
+    }
+
+    /// The neighbors of `point` within the grid that share a side with it.
+    pub fn neighbors4(&self, point: Point<i32>) -> impl Iterator<Item = Point<i32>> + '_ {
+        point.neighbors4().filter(|p| self.contains(*p))
+    }
+
+    /// The neighbors of `point` within the grid that share a side or a
+    /// corner with it.
+    pub fn neighbors8(&self, point: Point<i32>) -> impl Iterator<Item = Point<i32>> + '_ {
+        point.neighbors8().filter(|p| self.contains(*p))
+    }
+
+    /// Every tile, row by row, so that passes over whole rows or columns
+    /// can step through them without a bounds check per point.
+    pub fn tiles_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(self.height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Vec<&T>> {
+        (0..self.width).map(|x| self.column(x).collect())
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The grid mirrored along its diagonal, so that its rows become its
+    /// columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |p| Point::new(p.y, p.x))
+    }
+
+    /// The grid turned a quarter clockwise, its west side becoming its north.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height as i32;
+        self.remap(self.height, self.width, |p| {
+            Point::new(p.y, height - 1 - p.x)
+        })
+    }
+
+    /// The grid turned a quarter counterclockwise, its east side becoming
+    /// its north.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width as i32;
+        self.remap(self.height, self.width, |p| {
+            Point::new(width - 1 - p.y, p.x)
+        })
+    }
+
+    /// A grid of the given size whose tile at `p` is the tile of this grid
+    /// at `source(p)`.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(Point<i32>) -> Point<i32>) -> Self
+    where
+        T: Clone,
+    {
+        let mut grid = Grid {
+            cells: Vec::with_capacity(width * height),
+            width,
+            height,
+        };
+        for y in 0..height {
+            for x in 0..width {
+                let p = source(Point::new(x as i32, y as i32));
+                grid.cells.push(self[p].clone());
+            }
+        }
+        grid
+    }
+}
+
+impl<T> Index<Point<i32>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<i32>) -> &T {
+        match self.offset(point) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "({}, {}) is outside the {}x{} grid",
+                point.x, point.y, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point<i32>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<i32>) -> &mut T {
+        match self.offset(point) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "({}, {}) is outside the {}x{} grid",
+                point.x, point.y, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for tile in row {
+                write!(f, "{tile}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A grid of mostly empty tiles that only stores the tiles that differ from
+/// its `empty` tile, with the same API as `Grid`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point<i32>, T>,
+    empty: T,
+    width: usize,
+    height: usize,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(width: usize, height: usize, empty: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            empty,
+            width,
+            height,
+        }
+    }
+
+    /// Stores the tiles that are not `empty`. The grid is as wide as its
+    /// widest row.
+    pub fn from_rows(rows: Vec<Vec<T>>, empty: T) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut grid = Self::new(0, rows.len(), empty);
+        for (y, row) in rows.into_iter().enumerate() {
+            grid.width = grid.width.max(row.len());
+            for (x, tile) in row.into_iter().enumerate() {
+                if tile != grid.empty {
+                    grid.cells.insert(Point::new(x as i32, y as i32), tile);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Parses one tile per character and one row per line, skipping blank
+    /// lines, and stores the tiles that are not `empty`.
+    pub fn parse(text: &str, empty: T, tile: impl FnMut(char) -> T) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut tile = tile;
+        Self::from_rows(
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.chars().map(&mut tile).collect())
+                .collect(),
+            empty,
+        )
+    }
+
+    /// Sets the tile at `point`, growing the grid to hold it. The tile is
+    /// stored even if it is the empty one, until it is removed.
+    pub fn set(&mut self, point: Point<i32>, tile: T) {
+        assert!(point.x >= 0 && point.y >= 0, "negative point in a grid");
+        self.width = self.width.max(point.x as usize + 1);
+        self.height = self.height.max(point.y as usize + 1);
+        self.cells.insert(point, tile);
+    }
+
+    /// Turns the tile at `point` back into the empty one and returns the
+    /// tile stored there.
+    pub fn remove(&mut self, point: Point<i32>) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point<i32>) -> bool {
+        point.x >= 0
+            && point.y >= 0
+            && (point.x as usize) < self.width
+            && (point.y as usize) < self.height
+    }
+
+    /// The tile at `point`, `None` outside the grid.
+    pub fn get(&self, point: Point<i32>) -> Option<&T> {
+        self.contains(point)
+            .then(|| self.cells.get(&point).unwrap_or(&self.empty))
+    }
+
+    /// The tile at `point`, `None` outside the grid. An empty tile is stored
+    /// so that it can be changed.
+    pub fn get_mut(&mut self, point: Point<i32>) -> Option<&mut T>
+    where
+        T: Clone,
+    {
+        self.contains(point).then(|| {
+            self.cells
+                .entry(point)
+                .or_insert_with(|| self.empty.clone())
+        })
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point<i32>> {
+        let width = self.width;
+        (0..self.width * self.height)
+            .map(move |i| Point::new((i % width) as i32, (i / width) as i32))
+    }
+
+    /// Every tile with its point, row by row, the empty ones included.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i32>, &T)> {
+        self.positions().map(|p| (p, &self[p]))
+    }
+
+    /// The tiles that are not empty with their points, in no order.
+    pub fn stored(&self) -> impl Iterator<Item = (&Point<i32>, &T)> {
+        self.cells.iter()
+    }
+
+    /// The first point, row by row, whose tile matches.
+    pub fn find(&self, mut matches: impl FnMut(&T) -> bool) -> Option<Point<i32>> {
+        self.iter().find(|(_, t)| matches(t)).map(|(p, _)| p)
+    }
+
+    /// Every point, row by row, whose tile matches.
+    pub fn find_all<'a>(
+        &'a self,
+        mut matches: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point<i32>> + 'a {
+        self.iter().filter(move |(_, t)| matches(t)).map(|(p, _)| p)
+    }
+
+    /// The neighbors of `point` within the grid that share a side with it.
+    pub fn neighbors4(&self, point: Point<i32>) -> impl Iterator<Item = Point<i32>> + '_ {
+        point.neighbors4().filter(|p| self.contains(*p))
+    }
+
+    /// The neighbors of `point` within the grid that share a side or a
+    /// corner with it.
+    pub fn neighbors8(&self, point: Point<i32>) -> impl Iterator<Item = Point<i32>> + '_ {
+        point.neighbors8().filter(|p| self.contains(*p))
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        (0..self.width).map(move |x| &self[Point::new(x as i32, y as i32)])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = Vec<&T>> {
+        (0..self.height).map(|y| self.row(y).collect())
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        (0..self.height).map(move |y| &self[Point::new(x as i32, y as i32)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Vec<&T>> {
+        (0..self.width).map(|x| self.column(x).collect())
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> SparseGrid<U> {
+        SparseGrid {
+            cells: self.cells.iter().map(|(p, t)| (*p, f(t))).collect(),
+            empty: f(&self.empty),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The grid mirrored along its diagonal, so that its rows become its
+    /// columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |p| Point::new(p.y, p.x))
+    }
+
+    /// The grid turned a quarter clockwise, its west side becoming its north.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height as i32;
+        self.remap(self.height, self.width, |p| {
+            Point::new(height - 1 - p.y, p.x)
+        })
+    }
+
+    /// The grid turned a quarter counterclockwise, its east side becoming
+    /// its north.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width as i32;
+        self.remap(self.height, self.width, |p| {
+            Point::new(p.y, width - 1 - p.x)
+        })
+    }
+
+    /// A grid of the given size with each stored tile moved to `target(p)`.
+    fn remap(&self, width: usize, height: usize, target: impl Fn(Point<i32>) -> Point<i32>) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: self
+                .cells
+                .iter()
+                .map(|(p, t)| (target(*p), t.clone()))
+                .collect(),
+            empty: self.empty.clone(),
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<Point<i32>> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<i32>) -> &T {
+        self.get(point).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside the {}x{} grid",
+                point.x, point.y, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T: Clone> IndexMut<Point<i32>> for SparseGrid<T> {
+    fn index_mut(&mut self, point: Point<i32>) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside the {width}x{height} grid",
+                point.x, point.y
+            )
+        })
+    }
+}
+
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for tile in self.row(y) {
+                write!(f, "{tile}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEXT: &str = "ab.\n.c#\n";
+
+    fn dense() -> Grid<char> {
+        Grid::parse(TEXT, |c| c)
+    }
+
+    /// Empty tiles of the sparse grid are `'\0'`, shown as `.`.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    struct Tile(char);
+
+    impl Display for Tile {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", if self.0 == '\0' { '.' } else { self.0 })
+        }
+    }
+
+    fn sparse() -> SparseGrid<Tile> {
+        SparseGrid::parse(TEXT, Tile('\0'), |c| Tile(if c == '.' { '\0' } else { c }))
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = dense();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(1, 1)], 'c');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), TEXT);
+        assert_eq!(grid.find(|c| *c == '#'), Some(Point::new(2, 1)));
+        assert_eq!(grid.find_all(|c| *c == '.').count(), 2);
+        assert_eq!(grid.row(1), ['.', 'c', '#']);
+        assert_eq!(grid.column(2).collect::<String>(), ".#");
+
+        let mut grid = grid;
+        grid[Point::new(0, 1)] = 'd';
+        grid.tiles_mut()[2] = 'e';
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'e'], ['d', 'c', '#']]
+        );
+    }
+
+    #[test]
+    fn neighbors_within_bounds() {
+        let grid = dense();
+        let corner: Vec<_> = grid.neighbors4(Point::new(0, 0)).collect();
+        assert_eq!(corner, [Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbors8(Point::new(1, 0)).count(), 5);
+        assert_eq!(sparse().neighbors8(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn transpose_and_rotate() {
+        let grid = dense();
+        assert_eq!(grid.transpose().to_string(), "a.\nbc\n.#\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), ".a\ncb\n#.\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), ".#\nbc\na.\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn sparse_matches_dense() {
+        let (grid, sparse) = (dense(), sparse());
+        assert_eq!(sparse.stored().count(), 4);
+        assert_eq!(sparse.to_string(), grid.to_string());
+        for (dense, sparse) in [
+            (grid.transpose(), sparse.transpose()),
+            (grid.rotate_clockwise(), sparse.rotate_clockwise()),
+            (
+                grid.rotate_counterclockwise(),
+                sparse.rotate_counterclockwise(),
+            ),
+        ] {
+            assert_eq!(sparse.to_string(), dense.to_string());
+        }
+        assert_eq!(sparse.find(|t| t.0 == 'c'), Some(Point::new(1, 1)));
+        assert_eq!(
+            sparse.column(0).map(|t| t.to_string()).collect::<String>(),
+            "a."
+        );
+        assert_eq!(sparse.get(Point::new(0, 2)), None);
+    }
+
+    #[test]
+    fn sparse_changes_tiles() {
+        let mut grid = sparse();
+        grid[Point::new(0, 1)] = Tile('d');
+        *grid.get_mut(Point::new(0, 0)).unwrap() = Tile('e');
+        assert_eq!(grid.get_mut(Point::new(3, 0)), None);
+        grid.set(Point::new(3, 2), Tile('f'));
+        assert_eq!(grid.remove(Point::new(2, 1)), Some(Tile('#')));
+        assert_eq!(
+            grid.to_string(),
+            "eb..
+dc..
+...f
+"
+        );
+
+        let upper = grid.map(|t| t.0.to_ascii_uppercase());
+        assert_eq!(upper[Point::new(1, 0)], 'B');
+        assert_eq!(upper[Point::new(1, 2)], '\0');
+
+        let rows = vec![vec![Tile('a'), Tile('\0')], vec![Tile('\0')]];
+        let grid = SparseGrid::from_rows(rows, Tile('\0'));
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.stored().count(), 1);
+    }
+}
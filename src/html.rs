@@ -0,0 +1,202 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    End(String),
+    Text(String),
+}
+
+impl Token {
+    pub fn is_start(&self, tag: &str) -> bool {
+        matches!(self, Token::Start { name, .. } if name == tag)
+    }
+
+    pub fn is_end(&self, tag: &str) -> bool {
+        matches!(self, Token::End(name) if name == tag)
+    }
+
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        match self {
+            Token::Start { attributes, .. } => attributes
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a HTML page into tags and decoded text. This is only meant for the
+/// puzzle pages: comments, doctypes and the contents of `script` and `style`
+/// elements are dropped, and void elements yield just a start tag.
+pub fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(decode_entities(&rest[..start])));
+        }
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::End(name.trim().to_lowercase()));
+            continue;
+        }
+
+        let tag = tag.trim_end_matches('/');
+        let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        let name = name.to_lowercase();
+        if name == "script" || name == "style" {
+            let close = format!("</{name}>");
+            rest = rest
+                .find(&close)
+                .map_or("", |end| &rest[end + close.len()..]);
+            continue;
+        }
+        tokens.push(Token::Start {
+            name,
+            attributes: parse_attributes(attributes),
+        });
+    }
+
+    tokens
+}
+
+fn parse_attributes(s: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = s.trim();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let quote = after.chars().next().filter(|c| *c == '"' || *c == '\'');
+            let (raw, remaining) = match quote {
+                Some(q) => {
+                    let end = after[1..].find(q).map_or(after.len(), |e| e + 1);
+                    (&after[1..end], after.get(end + 1..).unwrap_or(""))
+                }
+                None => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            value = decode_entities(raw);
+            rest = remaining.trim_start();
+        }
+        if !key.is_empty() {
+            attributes.push((key, value));
+        }
+    }
+
+    attributes
+}
+
+pub fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut decoded = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let value = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => name
+                .strip_prefix("#x")
+                .or_else(|| name.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| name.strip_prefix('#').and_then(|d| d.parse().ok()))
+                .and_then(char::from_u32),
+        });
+        match (value, entity) {
+            (Some(c), Some((_, end))) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tags_and_text() {
+        let tokens = tokenize(
+            "<!DOCTYPE html><article class=\"day-desc\"><p>a &lt; b<!-- x --></p><br/>\
+             <script>if (a < b) {}</script></article>",
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Start {
+                    name: "article".to_string(),
+                    attributes: vec![("class".to_string(), "day-desc".to_string())],
+                },
+                Token::Start {
+                    name: "p".to_string(),
+                    attributes: vec![],
+                },
+                Token::Text("a < b".to_string()),
+                Token::End("p".to_string()),
+                Token::Start {
+                    name: "br".to_string(),
+                    attributes: vec![],
+                },
+                Token::End("article".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(
+            decode_entities("&quot;&#35;&#x41;&amp;&unknown;"),
+            "\"#A&&unknown;"
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use std::fmt::Display;
+use std::ops::Deref;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{input_exists, read_input, Grid};
+
+/// A puzzle input, split into lines and blank-line separated sections that
+/// remember where they are in the input, so that parse errors can name the
+/// line at fault.
+///
+/// ```ignore
+/// let input = PuzzleInput::load(input, INPUT);
+/// let (header, body) = input.split_header()?;
+/// for line in body.lines() {
+///     let (key, value) = line.key_value(':')?;
+///     let numbers: Vec<i64> = value.ints()?;
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PuzzleInput {
+    text: String,
+    first_line: usize,
+}
+
+/// A line of a puzzle input and its number, counted from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl PuzzleInput {
+    /// The input with a byte order mark dropped, CRLF line endings turned
+    /// into LF and the trailing blank lines removed.
+    pub fn new(text: &str) -> Self {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        Self {
+            text: text.replace("\r\n", "\n").trim_end().to_string(),
+            first_line: 1,
+        }
+    }
+
+    pub fn read<T: AsRef<Path>>(pathname: T) -> std::io::Result<Self> {
+        read_input(pathname).map(|text| Self::new(&text))
+    }
+
+    /// The input of a day: `input` is either the path of an input file or
+    /// the input itself, and `default` the path of the day's own input.
+    pub fn load(input: Option<&str>, default: &str) -> Self {
+        match input {
+            Some(text) if !input_exists(text) => Self::new(text),
+            _ => Self::read(input.unwrap_or(default)).expect("unable to open file"),
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Every line, blank ones included.
+    pub fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        self.text
+            .split('\n')
+            .enumerate()
+            .filter(|_| !self.is_empty())
+            .map(|(i, text)| Line {
+                number: self.first_line + i,
+                text,
+            })
+    }
+
+    /// The blocks of lines between blank lines.
+    pub fn sections(&self) -> Vec<PuzzleInput> {
+        let mut sections = Vec::new();
+        let mut block: Option<(Line, Line)> = None;
+        for line in self.lines() {
+            if line.trim().is_empty() {
+                if let Some((first, last)) = block.take() {
+                    sections.push(self.slice(first, last));
+                }
+                continue;
+            }
+            block = Some((block.map_or(line, |(first, _)| first), line));
+        }
+        if let Some((first, last)) = block {
+            sections.push(self.slice(first, last));
+        }
+        sections
+    }
+
+    /// The first line, and the lines after it without the blank lines that
+    /// separate them, e.g. the name of a map and its entries.
+    pub fn split_header(&self) -> Result<(Line<'_>, PuzzleInput), InputError> {
+        let mut lines = self.lines();
+        let header = lines
+            .next()
+            .ok_or_else(|| self.error("expected a header line"))?;
+        let body = match lines.find(|line| !line.trim().is_empty()) {
+            Some(first) => self.slice(first, lines.last().unwrap_or(first)),
+            None => PuzzleInput {
+                text: String::new(),
+                first_line: header.number + 1,
+            },
+        };
+        Ok((header, body))
+    }
+
+    /// One tile per character and one row per line. `tile` returns `None`
+    /// for characters that are not tiles.
+    pub fn grid<T>(&self, mut tile: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, InputError> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for line in self.lines() {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    tile(c).ok_or_else(|| {
+                        line.error(format!("unexpected tile `{c}` in column {}", x + 1))
+                    })
+                })
+                .collect::<Result<Vec<T>, InputError>>()?;
+            if let Some(first) = rows.first() {
+                if row.len() != first.len() {
+                    return Err(line.error(format!(
+                        "row is {} tiles wide, the first row {}",
+                        row.len(),
+                        first.len()
+                    )));
+                }
+            }
+            rows.push(row);
+        }
+        if rows.is_empty() {
+            return Err(self.error("expected a grid"));
+        }
+        Ok(Grid::from_rows(rows))
+    }
+
+    /// An error about the input as a whole, reported at its first line.
+    pub fn error(&self, message: impl Display) -> InputError {
+        InputError {
+            line: self.first_line,
+            message: message.to_string(),
+        }
+    }
+
+    /// The lines from `first` to `last`, both lines of this input.
+    fn slice(&self, first: Line, last: Line) -> PuzzleInput {
+        let start = self.offset(first);
+        let end = self.offset(last) + last.text.len();
+        PuzzleInput {
+            text: self.text[start..end].to_string(),
+            first_line: first.number,
+        }
+    }
+
+    /// Where `line`, a line of this input, starts in its text.
+    fn offset(&self, line: Line) -> usize {
+        line.text.as_ptr() as usize - self.text.as_ptr() as usize
+    }
+}
+
+impl FromStr for PuzzleInput {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+impl Display for PuzzleInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl<'a> Line<'a> {
+    pub fn error(&self, message: impl Display) -> InputError {
+        InputError {
+            line: self.number,
+            message: message.to_string(),
+        }
+    }
+
+    /// The trimmed line parsed as a `T`.
+    pub fn parse<T: FromStr>(&self) -> Result<T, InputError>
+    where
+        T::Err: Display,
+    {
+        self.text
+            .trim()
+            .parse()
+            .map_err(|e| self.error(format!("`{}`: {e}", self.text.trim())))
+    }
+
+    /// The integers in the line, in order, ignoring the text around them.
+    /// A `-` is the sign of the number after it, unless it follows a digit
+    /// as in the range `1-3`.
+    pub fn ints<T: FromStr>(&self) -> Result<Vec<T>, InputError>
+    where
+        T::Err: Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut ints = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let signed = i > 0 && bytes[i - 1] == b'-' && (i < 2 || !bytes[i - 2].is_ascii_digit());
+            let start = if signed { i - 1 } else { i };
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            let number = &self.text[start..i];
+            ints.push(
+                number
+                    .parse()
+                    .map_err(|e| self.error(format!("`{number}`: {e}")))?,
+            );
+        }
+        Ok(ints)
+    }
+
+    /// The trimmed text before and after the first `separator`, as lines of
+    /// their own for further parsing.
+    pub fn key_value(&self, separator: char) -> Result<(Line<'a>, Line<'a>), InputError> {
+        let (key, value) = self
+            .text
+            .split_once(separator)
+            .ok_or_else(|| self.error(format!("expected `{separator}` in `{}`", self.text)))?;
+        let line = |text: &'a str| Line {
+            number: self.number,
+            text: text.trim(),
+        };
+        Ok((line(key), line(value)))
+    }
+}
+
+impl Deref for Line<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+impl Display for Line<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalizes_and_splits_sections() {
+        let input = PuzzleInput::new(
+            "\u{feff}seeds: 79 14\r\n\r\n\r\nsoil map:\r\n50 98 2\r\n52 50 48\r\n\r\n",
+        );
+        assert_eq!(
+            input.text(),
+            "seeds: 79 14\n\n\nsoil map:\n50 98 2\n52 50 48"
+        );
+        assert_eq!(input.lines().count(), 6);
+
+        let sections = input.sections();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].text(), "seeds: 79 14");
+        let (header, body) = sections[1].split_header().unwrap();
+        assert_eq!((header.number, header.text), (4, "soil map:"));
+        let numbers: Vec<Vec<u64>> = body.lines().map(|l| l.ints().unwrap()).collect();
+        assert_eq!(numbers, vec![vec![50, 98, 2], vec![52, 50, 48]]);
+        assert_eq!(
+            body.lines().map(|l| l.number).collect::<Vec<_>>(),
+            vec![5, 6]
+        );
+
+        let (header, body) = input.split_header().unwrap();
+        assert_eq!(header.text, "seeds: 79 14");
+        assert_eq!(body.lines().next().unwrap().number, 4);
+        assert!(PuzzleInput::new("").sections().is_empty());
+        assert!(PuzzleInput::new("\n\n").split_header().is_err());
+    }
+
+    #[test]
+    fn parses_lines() {
+        let input = PuzzleInput::new("Card 1: 41 -48 | 83\nx=-3..5, y=1-3\nname: 12a");
+        let lines: Vec<Line> = input.lines().collect();
+        let (key, value) = lines[0].key_value(':').unwrap();
+        assert_eq!((key.text, value.text), ("Card 1", "41 -48 | 83"));
+        assert_eq!(value.ints::<i32>().unwrap(), vec![41, -48, 83]);
+        assert_eq!(lines[1].ints::<i64>().unwrap(), vec![-3, 5, 1, 3]);
+
+        let error = lines[0].ints::<u32>().unwrap_err();
+        assert_eq!(error.line, 1);
+        assert_eq!(
+            error.to_string(),
+            "line 1: `-48`: invalid digit found in string"
+        );
+        let error = lines[2].key_value('=').unwrap_err();
+        assert_eq!(error.to_string(), "line 3: expected `=` in `name: 12a`");
+        let (_, value) = lines[2].key_value(':').unwrap();
+        assert_eq!(value.parse::<u8>().unwrap_err().line, 3);
+    }
+
+    #[test]
+    fn parses_grids() {
+        let tile = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        let input = PuzzleInput::new("#.#\n..#\n\n##.\n.x.\n\n#.\n...");
+        let sections = input.sections();
+        let grid = sections[0].grid(tile).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(
+            sections[1].grid(tile).unwrap_err().to_string(),
+            "line 5: unexpected tile `x` in column 2"
+        );
+        assert_eq!(
+            sections[2].grid(tile).unwrap_err().to_string(),
+            "line 8: row is 3 tiles wide, the first row 2"
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use std::fmt::Display;
+use std::ops::{Add, Range, Sub};
+
+/// The values from `start` up to but not including `end`, empty unless
+/// `start < end`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// The `length` values from `start`.
+    pub fn with_length(start: T, length: T) -> Self {
+        Self::new(start, start + length)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.start <= *value && *value < self.end
+    }
+
+    /// The values in both, `None` if there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let overlap = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The values in either, `None` unless they overlap or touch, so that
+    /// they coalesce into one interval.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        (self.start <= other.end && other.start <= self.end)
+            .then(|| Self::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// The values before, within and after `other`, each `None` when empty.
+    pub fn split(&self, other: &Self) -> [Option<Self>; 3] {
+        let part = |start: T, end: T| {
+            let part = Self::new(start.max(self.start), end.min(self.end));
+            (!part.is_empty()).then_some(part)
+        };
+        if other.is_empty() {
+            return [part(self.start, self.end), None, None];
+        }
+        [
+            part(self.start, other.start),
+            part(other.start, other.end),
+            part(other.end, self.end),
+        ]
+    }
+
+    /// The values not in `other`, at most one interval before and one after
+    /// it.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let [before, _, after] = self.split(other);
+        before.into_iter().chain(after).collect()
+    }
+
+    /// The interval moved by `to - from`, as a mapping of `from` onto `to`,
+    /// without the intermediate values unsigned types cannot hold.
+    pub fn shift(&self, from: T, to: T) -> Self {
+        let moved = |value: T| {
+            if to >= from {
+                value + (to - from)
+            } else {
+                value - (from - to)
+            }
+        };
+        Self::new(moved(self.start), moved(self.end))
+    }
+
+    /// The values in order.
+    pub fn iter(&self) -> Range<T>
+    where
+        Range<T>: Iterator<Item = T>,
+    {
+        self.start..self.end
+    }
+}
+
+/// Values as the fewest disjoint intervals, kept in order, with touching
+/// and overlapping intervals coalesced.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> FromIterator<Interval<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Self {
+        let mut intervals: Vec<Interval<T>> =
+            intervals.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort_by_key(|i| i.start);
+        let mut coalesced: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match coalesced.last_mut() {
+                Some(last) if last.union(&interval).is_some() => {
+                    *last = last.union(&interval).unwrap();
+                }
+                _ => coalesced.push(interval),
+            }
+        }
+        Self {
+            intervals: coalesced,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(interval: Interval<T>) -> Self {
+        [interval].into_iter().collect()
+    }
+}
+
+impl<T: Display> Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let intervals: Vec<String> = self.intervals.iter().map(|i| i.to_string()).collect();
+        write!(f, "{{{}}}", intervals.join(", "))
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of values.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::default(), |total, i| total + i.len())
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    /// The values in order.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_
+    where
+        Range<T>: Iterator<Item = T>,
+    {
+        self.intervals.iter().flat_map(Interval::iter)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        // The intervals are ordered, so the only candidate is the last one
+        // starting at or before `value`.
+        let after = self.intervals.partition_point(|i| i.start <= *value);
+        after > 0 && self.intervals[after - 1].contains(value)
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        *self = self.intervals.iter().copied().chain([interval]).collect();
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .copied()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        // Walks both ordered lists at once, advancing whichever ends first.
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            intervals.extend(a.intersection(&b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        intervals.into_iter().collect()
+    }
+
+    /// The values not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut remaining = self.intervals.clone();
+        for cut in &other.intervals {
+            remaining = remaining.iter().flat_map(|i| i.difference(cut)).collect();
+        }
+        remaining.into_iter().collect()
+    }
+
+    /// All intervals moved by `to - from`, see [`Interval::shift`].
+    pub fn shift(&self, from: T, to: T) -> Self {
+        self.intervals.iter().map(|i| i.shift(from, to)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::Rng;
+
+    const CASES: usize = 500;
+
+    fn set(intervals: &[(u32, u32)]) -> IntervalSet<u32> {
+        intervals
+            .iter()
+            .map(|(s, e)| Interval::new(*s, *e))
+            .collect()
+    }
+
+    fn random_set(rng: &mut Rng) -> IntervalSet<u32> {
+        (0..rng.below(5))
+            .map(|_| {
+                let start = rng.range(0, 40) as u32;
+                Interval::with_length(start, rng.range(0, 10) as u32)
+            })
+            .collect()
+    }
+
+    fn model(set: &IntervalSet<u32>) -> BTreeSet<u32> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn intervals() {
+        let a = Interval::new(2, 8);
+        assert_eq!(a.len(), 6);
+        assert!(a.contains(&2) && !a.contains(&8));
+        assert_eq!(Interval::new(5, 3).len(), 0);
+        assert_eq!(
+            a.intersection(&Interval::new(6, 10)),
+            Some(Interval::new(6, 8))
+        );
+        assert_eq!(a.intersection(&Interval::new(8, 10)), None);
+        assert_eq!(a.union(&Interval::new(8, 10)), Some(Interval::new(2, 10)));
+        assert_eq!(a.union(&Interval::new(9, 10)), None);
+        assert_eq!(
+            a.split(&Interval::new(4, 6)),
+            [
+                Some(Interval::new(2, 4)),
+                Some(Interval::new(4, 6)),
+                Some(Interval::new(6, 8))
+            ]
+        );
+        assert_eq!(
+            a.split(&Interval::new(0, 5)),
+            [None, Some(Interval::new(2, 5)), Some(Interval::new(5, 8))]
+        );
+        assert_eq!(
+            a.difference(&Interval::new(4, 6)),
+            vec![Interval::new(2, 4), Interval::new(6, 8)]
+        );
+        assert_eq!(a.shift(2, 50), Interval::new(50, 56));
+        assert_eq!(a.shift(50, 48), Interval::new(0, 6));
+        assert_eq!(a.iter().sum::<u32>(), 2 + 3 + 4 + 5 + 6 + 7);
+        assert_eq!(Interval::from(1..3).to_string(), "[1, 3)");
+    }
+
+    #[test]
+    fn sets() {
+        let s = set(&[(5, 8), (0, 2), (2, 3), (7, 10), (12, 12)]);
+        assert_eq!(s.intervals(), &[Interval::new(0, 3), Interval::new(5, 10)]);
+        assert_eq!(s.to_string(), "{[0, 3), [5, 10)}");
+        assert_eq!(s.len(), 8);
+        assert_eq!(s.min(), Some(0));
+        assert!(s.contains(&9) && !s.contains(&3) && !s.contains(&10));
+        let mut t = s.clone();
+        t.insert(Interval::new(3, 5));
+        assert_eq!(t, set(&[(0, 10)]));
+        assert_eq!(s.difference(&set(&[(1, 6)])), set(&[(0, 1), (6, 10)]));
+        assert_eq!(s.shift(0, 100), set(&[(100, 103), (105, 110)]));
+    }
+
+    #[test]
+    fn set_operations_match_model() {
+        let mut rng = Rng::new(48);
+        for _ in 0..CASES {
+            let (a, b) = (random_set(&mut rng), random_set(&mut rng));
+            let (ma, mb) = (model(&a), model(&b));
+
+            assert_eq!(model(&a.union(&b)), &ma | &mb);
+            assert_eq!(model(&a.intersection(&b)), &ma & &mb);
+            assert_eq!(model(&a.difference(&b)), &ma - &mb);
+            assert_eq!(a.len() as usize, ma.len());
+            for value in 0..60 {
+                assert_eq!(a.contains(&value), ma.contains(&value));
+            }
+            assert_eq!(
+                model(&a.shift(5, 12)),
+                ma.iter().map(|v| v + 7).collect::<BTreeSet<_>>()
+            );
+
+            // Always coalesced: ordered, non-empty and not touching.
+            let union = a.union(&b);
+            assert!(union.intervals().iter().all(|i| !i.is_empty()));
+            assert!(union.intervals().windows(2).all(|w| w[0].end < w[1].start));
+
+            let mut inserted = a.clone();
+            for interval in b.intervals() {
+                inserted.insert(*interval);
+            }
+            assert_eq!(inserted, union);
+        }
+    }
+
+    #[test]
+    fn splits_match_model() {
+        let mut rng = Rng::new(48);
+        for _ in 0..CASES {
+            let random = |rng: &mut Rng| {
+                let start = rng.range(0, 30) as u32;
+                Interval::with_length(start, rng.range(0, 15) as u32)
+            };
+            let (a, b) = (random(&mut rng), random(&mut rng));
+            let parts = a.split(&b);
+            let values =
+                |part: Option<Interval<u32>>| part.map(|p| p.iter().collect()).unwrap_or_default();
+            let [before, within, after]: [Vec<u32>; 3] = parts.map(values);
+            assert_eq!(
+                within,
+                a.iter().filter(|v| b.contains(v)).collect::<Vec<_>>()
+            );
+            assert!(before.iter().all(|v| *v < b.start || b.is_empty()));
+            assert!(after.iter().all(|v| *v >= b.end && !b.is_empty()));
+            let all: Vec<u32> = [before, within, after].concat();
+            assert_eq!(all, a.iter().collect::<Vec<_>>());
+        }
+    }
+}
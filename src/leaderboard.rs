@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use colored::*;
+use serde::{Deserialize, Serialize};
+
+/// A private leaderboard as exported from the leaderboard's JSON link.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Standing {
+    pub rank: usize,
+    pub name: String,
+    pub stars: u32,
+    pub local_score: u32,
+    pub delta_score: u32,
+    pub delta_rank: usize,
+    pub days: Vec<DayTimes>,
+}
+
+/// Seconds from the unlock of a day to each star, and from the first star to
+/// the second.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DayTimes {
+    pub day: u8,
+    pub part1: Option<i64>,
+    pub part2: Option<i64>,
+    pub delta: Option<i64>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    fn star_ts(&self, day: u8, part: u8) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.to_string())
+            .and_then(|d| d.get(&part.to_string()))
+            .map(|s| s.get_star_ts)
+    }
+
+    fn day_times(&self, year: i64) -> Vec<DayTimes> {
+        (1..=25)
+            .filter_map(|day| {
+                let unlock = unlock_ts(year, day);
+                let part1 = self.star_ts(day, 1)?;
+                let part2 = self.star_ts(day, 2);
+                Some(DayTimes {
+                    day,
+                    part1: Some(part1 - unlock),
+                    part2: part2.map(|ts| ts - unlock),
+                    delta: part2.map(|ts| ts - part1),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Leaderboard {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    fn year(&self) -> i64 {
+        self.event.parse().unwrap_or(2023)
+    }
+
+    /// Local score as the official one: on each day and part, the first of
+    /// N members to get the star gets N points, the second N - 1 and so on.
+    pub fn local_scores(&self) -> HashMap<u64, u32> {
+        self.score_by(|m, day| {
+            [m.star_ts(day, 1), m.star_ts(day, 2)]
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .collect()
+        })
+    }
+
+    /// Alternative local score that only rewards part two: on each day, the
+    /// member with the shortest time from the first star to the second gets
+    /// N points, the next one N - 1 and so on.
+    pub fn delta_scores(&self) -> HashMap<u64, u32> {
+        self.score_by(|m, day| match (m.star_ts(day, 1), m.star_ts(day, 2)) {
+            (Some(p1), Some(p2)) => vec![(0, p2 - p1)],
+            _ => vec![],
+        })
+    }
+
+    fn score_by<F>(&self, key: F) -> HashMap<u64, u32>
+    where
+        F: Fn(&Member, u8) -> Vec<(usize, i64)>,
+    {
+        let mut scores: HashMap<u64, u32> = self.members.values().map(|m| (m.id, 0)).collect();
+        let count = self.members.len() as u32;
+
+        for day in 1..=25 {
+            let mut finishers: HashMap<usize, Vec<(i64, u64)>> = HashMap::new();
+            for member in self.members.values() {
+                for (part, value) in key(member, day) {
+                    finishers.entry(part).or_default().push((value, member.id));
+                }
+            }
+            for mut finishers in finishers.into_values() {
+                finishers.sort();
+                for (rank, (_, id)) in finishers.iter().enumerate() {
+                    *scores.get_mut(id).unwrap() += count - rank as u32;
+                }
+            }
+        }
+        scores
+    }
+
+    /// Members ordered by their local score, then by their stars.
+    pub fn standings(&self) -> Vec<Standing> {
+        let year = self.year();
+        let delta_scores = self.delta_scores();
+
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|m| (delta_scores[&m.id], m.id));
+        members.reverse();
+        let delta_ranks: HashMap<u64, usize> = members
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.id, i + 1))
+            .collect();
+
+        members.sort_by_key(|m| (m.local_score, m.stars, m.id));
+        members.reverse();
+        members
+            .iter()
+            .enumerate()
+            .map(|(i, m)| Standing {
+                rank: i + 1,
+                name: m.display_name(),
+                stars: m.stars,
+                local_score: m.local_score,
+                delta_score: delta_scores[&m.id],
+                delta_rank: delta_ranks[&m.id],
+                days: m.day_times(year),
+            })
+            .collect()
+    }
+}
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+pub fn unlock_ts(year: i64, day: u8) -> i64 {
+    days_from_civil(year, 12, day as i64) * 86_400 + 5 * 3_600
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+pub fn format_duration(seconds: i64) -> String {
+    let (h, m, s) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    format!("{h}:{m:02}:{s:02}")
+}
+
+pub fn render_standings(standings: &[Standing]) -> String {
+    let mut out = String::new();
+    let width = standings.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let optional = |t: Option<i64>| t.map(format_duration).unwrap_or_default();
+
+    for s in standings {
+        let _ = writeln!(
+            out,
+            "{:>3}) {:width$} {:>2}{} {:>5} {} {:>5} {}",
+            s.rank,
+            s.name.bold(),
+            s.stars,
+            "*".yellow().bold(),
+            s.local_score,
+            "local".blue(),
+            s.delta_score,
+            format!("delta (#{})", s.delta_rank).blue(),
+        );
+        for d in &s.days {
+            let _ = writeln!(
+                out,
+                "{:9}{} {:>2}  {:>10}  {:>10}  {} {:>9}",
+                "",
+                "Day".blue(),
+                d.day,
+                optional(d.part1),
+                optional(d.part2),
+                "\u{394}".blue(),
+                optional(d.delta),
+            );
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXPORT: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 4, "local_score": 5, "global_score": 0,
+                "last_star_ts": 1701493200,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1701407100, "star_index": 1},
+                          "2": {"get_star_ts": 1701408000, "star_index": 2}},
+                    "2": {"1": {"get_star_ts": 1701493500, "star_index": 5},
+                          "2": {"get_star_ts": 1701497100, "star_index": 6}}
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 3, "local_score": 6, "global_score": 0,
+                "last_star_ts": 1701493400,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1701407000, "star_index": 0},
+                          "2": {"get_star_ts": 1701407300, "star_index": 3}},
+                    "2": {"1": {"get_star_ts": 1701493400, "star_index": 4}}
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn unlock_time() {
+        assert_eq!(unlock_ts(2023, 1), 1701406800);
+        assert_eq!(unlock_ts(2023, 25), 1703480400);
+    }
+
+    #[test]
+    fn scores_and_standings() {
+        let leaderboard = Leaderboard::from_json(EXPORT).unwrap();
+        assert_eq!(leaderboard.local_scores(), HashMap::from([(1, 5), (2, 6)]));
+        assert_eq!(leaderboard.delta_scores(), HashMap::from([(1, 3), (2, 2)]));
+
+        let standings = leaderboard.standings();
+        assert_eq!(standings[0].name, "(anonymous user #2)");
+        assert_eq!(standings[0].delta_rank, 2);
+        assert_eq!(standings[1].name, "alice");
+        assert_eq!(standings[1].delta_rank, 1);
+        assert_eq!(
+            standings[1].days[0],
+            DayTimes {
+                day: 1,
+                part1: Some(300),
+                part2: Some(1200),
+                delta: Some(900),
+            }
+        );
+        assert_eq!(format_duration(3723), "1:02:03");
+    }
+}
@@ -1,13 +1,63 @@
 use colored::*;
 use itertools::Itertools;
+use std::cell::{Cell, RefCell};
 use std::fmt::{Debug, Display};
-use std::fs::read_to_string;
-use std::path::Path;
+use std::fs::read_dir;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Once;
 use std::time::{Duration, Instant};
 
+mod answers;
+mod assumptions;
+mod budget;
+mod crosscheck;
+mod crypt;
+mod cuboid;
+mod differential;
+mod direction;
+mod fuzzing;
+mod generate;
+mod geometry;
+mod grid;
+pub mod html;
+mod input;
+mod interval;
+mod leaderboard;
+mod math;
+mod memo;
+mod minimize;
 mod point;
+mod point3;
+mod profile;
+mod puzzle;
+mod search;
+mod snapshot;
+pub use answers::*;
+pub use assumptions::*;
+pub use budget::*;
+pub use crosscheck::*;
+pub use crypt::*;
+pub use cuboid::*;
+pub use differential::*;
+pub use direction::*;
+pub use fuzzing::*;
+pub use generate::*;
+pub use geometry::*;
+pub use grid::*;
+pub use input::*;
+pub use interval::*;
+pub use leaderboard::*;
+pub use math::*;
+pub use memo::*;
+pub use minimize::*;
 pub use point::*;
+pub use point3::*;
+pub use profile::*;
+pub use puzzle::*;
+pub use search::*;
+pub use snapshot::*;
 
 pub enum Selector {
     All,
@@ -34,6 +84,111 @@ pub trait Runner {
     fn parse(&mut self, input: Option<&str>);
     fn part1(&mut self) -> Vec<String>;
     fn part2(&mut self) -> Vec<String>;
+
+    /// Other implementations of the parts, which the differential tests
+    /// check against `part1` and `part2`.
+    fn alternatives(&self) -> Vec<Alternative> {
+        Vec::new()
+    }
+
+    fn run_alternative(&mut self, alternative: &Alternative) -> Vec<String> {
+        panic!(
+            "No implementation {} of part {}",
+            alternative.name, alternative.part
+        )
+    }
+
+    /// Time each phase is expected to take on the real input, checked by the
+    /// opt-in budget tests.
+    fn budget(&self) -> Budget {
+        Budget::default()
+    }
+
+    /// Checks of the properties of the parsed input that the parts rely on,
+    /// run by `verify-input`.
+    fn assumptions(&self) -> Vec<Assumption> {
+        Vec::new()
+    }
+
+    /// A random well-formed input of the day, with `size` scaling its number
+    /// of lines or its grid. `None` for days without a generator.
+    fn generate(&self, _rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+/// Generates the tests of a day from a table with one row per test:
+///
+/// ```ignore
+/// solution_tests! {
+///     Day01, INPUT;
+///     part1_test_works: example(TEST_INPUT), part1 => answer("142");
+///     part1_works: real, part1 => answer("54081");
+///     part2_works: real, part2 => unsolved;
+/// }
+/// ```
+///
+/// `real` parses the day's own input, which is skipped when it is not
+/// available. `unsolved`, `slow("...")` and `wrong("...")` rows are ignored
+/// tests, so that `cargo test` reports which parts are not solved yet, too
+/// slow to test, or known to give a wrong answer. `--ignored` runs them all.
+#[macro_export]
+macro_rules! solution_tests {
+    ($day:ident, $real:expr; $(
+        $name:ident: $source:ident $(($input:expr))?, $part:ident => $expect:ident $(($answer:expr))?;
+    )+) => {
+        $(
+            $crate::solution_tests!(
+                @$expect $day, $name, $part,
+                $crate::solution_tests!(@$source $real $(, $input)?)
+                $(, $answer)?
+            );
+        )+
+    };
+    (@real $real:expr) => {
+        (None, $real)
+    };
+    (@example $real:expr, $input:expr) => {
+        (Some($input), $input)
+    };
+    (@answer $day:ident, $name:ident, $part:ident, $source:expr, $answer:expr) => {
+        #[test]
+        fn $name() {
+            $crate::solution_tests!(@run $day, $part, $source, $answer);
+        }
+    };
+    (@slow $day:ident, $name:ident, $part:ident, $source:expr, $answer:expr) => {
+        #[test]
+        #[ignore = "slow"]
+        fn $name() {
+            $crate::solution_tests!(@run $day, $part, $source, $answer);
+        }
+    };
+    (@wrong $day:ident, $name:ident, $part:ident, $source:expr, $answer:expr) => {
+        #[test]
+        #[ignore = "wrong answer"]
+        fn $name() {
+            $crate::solution_tests!(@run $day, $part, $source, $answer);
+        }
+    };
+    (@unsolved $day:ident, $name:ident, $part:ident, $source:expr) => {
+        #[test]
+        #[ignore = "unsolved"]
+        fn $name() {
+            let unsolved = $crate::OutputStatus::Unsolved.to_string();
+            $crate::solution_tests!(@run $day, $part, $source, unsolved);
+        }
+    };
+    (@run $day:ident, $part:ident, $source:expr, $answer:expr) => {
+        let (input, path): (Option<&str>, &str) = $source;
+        if input.is_none() && !$crate::input_available(path) {
+            return;
+        }
+        let mut day = $day::new();
+        $crate::Runner::parse(&mut day, input);
+        let output = $crate::Runner::$part(&mut day);
+        assert_eq!(output[0], $answer)
+    };
 }
 
 pub fn output<T: Display>(output: T) -> Vec<String> {
@@ -99,6 +254,171 @@ pub fn run_solution_with_part<T: Runner + ?Sized>(solution: &mut T, part: u8) {
     }
 }
 
+/// Runs a day against each of `inputs` with a fresh solution, and prints a
+/// matrix of the answers checked against the answers recorded for each input.
+pub fn run_solution_on_inputs<F>(new_solution: F, inputs: &[PathBuf])
+where
+    F: Fn() -> Box<dyn Runner>,
+{
+    let name = new_solution().name();
+    println!(
+        "\n{}{}{}{}{}",
+        "---- ".green().bold(),
+        name.0.to_string().green().bold(),
+        ", Day ".green().bold(),
+        name.1.to_string().green().bold(),
+        " ----".green().bold(),
+    );
+
+    if inputs.is_empty() {
+        println!("\t{}", "No inputs found".yellow());
+        return;
+    }
+
+    let width = inputs
+        .iter()
+        .map(|i| i.display().to_string().len())
+        .max()
+        .unwrap_or(0);
+    println!(
+        "\t{:width$}  {:24}{}",
+        "Input".blue().bold(),
+        "Part 1".blue().bold(),
+        "Part 2".blue().bold(),
+    );
+
+    for input in inputs {
+        let path = input.to_string_lossy();
+        let file = input
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let answers = AnswerStore::for_input(input);
+
+        let outputs = catch_panic(|| {
+            let mut solution = new_solution();
+            solution.parse(Some(&path));
+            let p1 = solution.part1();
+            let p2 = solution.part2();
+            (p1, p2)
+        });
+
+        print!("\t{:width$}  ", path);
+        match outputs {
+            Some((p1, p2)) => {
+                print!("{:24}", check_answer(&p1, answers.get(&file, 1)));
+                println!("{}", check_answer(&p2, answers.get(&file, 2)));
+            }
+            None => println!("{}", OutputStatus::Failed.to_string().red().bold()),
+        }
+    }
+}
+
+fn check_answer(output: &[String], expected: Option<&str>) -> ColoredString {
+    let answer = output.first().map(|s| s.as_str()).unwrap_or_default();
+    match expected {
+        Some(expected) if expected == answer => format!("{answer} \u{2713}").green(),
+        Some(expected) => format!("{answer} \u{2717} ({expected})").red(),
+        None => answer.normal(),
+    }
+}
+
+thread_local! {
+    /// How many `catch_panic` calls the thread is inside of.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// Source location of the last panic caught on the thread.
+    static CAUGHT_AT: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Installs the panic hook once for the whole process, rather than swapping
+/// it around each call, which races with other threads that panic or catch
+/// panics. It reports a panic with the previous hook unless its thread is
+/// inside `catch_panic`.
+fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let report = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() == 0 {
+                report(info);
+                return;
+            }
+            let location = info
+                .location()
+                .map(|l| format!("{}:{}", l.file(), l.line()))
+                .unwrap_or_default();
+            CAUGHT_AT.set(location);
+        }));
+    });
+}
+
+/// Runs `f`, returning `None` instead of unwinding if it panics. The panic
+/// message is not printed.
+pub fn catch_panic<R, F: FnOnce() -> R>(f: F) -> Option<R> {
+    catch_panic_location(f).ok()
+}
+
+/// Runs `f`, returning the source location of its panic, like
+/// `src/solutions/day01.rs:42`, instead of unwinding.
+pub fn catch_panic_location<R, F: FnOnce() -> R>(f: F) -> Result<R, String> {
+    install_panic_hook();
+    CATCHING.set(CATCHING.get() + 1);
+    let result = catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(CATCHING.get() - 1);
+    result.map_err(|_| CAUGHT_AT.take())
+}
+
+/// Runs `f` with colors turned off, for tests that compare rendered text.
+/// Calls take turns, and colors go back to the terminal's own setting
+/// afterwards, even if `f` panics.
+#[cfg(test)]
+pub(crate) fn without_colors<R>(f: impl FnOnce() -> R) -> R {
+    static COLORS: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    struct Restore;
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            colored::control::unset_override();
+        }
+    }
+    let _turn = COLORS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    colored::control::set_override(false);
+    let _restore = Restore;
+    f()
+}
+
+/// Finds every `dayNN*.txt` file for `day` under `dir`, searching recursively.
+/// Encrypted `dayNN*.txt.enc` files are found as their plaintext path.
+pub fn find_inputs<T: AsRef<Path>>(dir: T, day: usize) -> Vec<PathBuf> {
+    let prefix = format!("day{day:02}");
+    let mut inputs = Vec::new();
+    let Ok(entries) = read_dir(dir) else {
+        return inputs;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            inputs.extend(find_inputs(&path, day));
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with(&prefix) {
+            continue;
+        }
+        if name.ends_with(".txt") {
+            inputs.push(path);
+        } else if name.ends_with(".txt.enc") {
+            inputs.push(path.with_extension(""));
+        }
+    }
+
+    inputs.sort();
+    inputs.dedup();
+    inputs
+}
+
 fn print_solution(which: usize, output: &[String], duration: Duration) {
     let ms = duration.as_millis();
     let sec_part = ms / 1000;
@@ -119,7 +439,7 @@ fn print_solution(which: usize, output: &[String], duration: Duration) {
 }
 
 pub fn read_to_chars<T: AsRef<Path>>(pathname: T) -> Vec<char> {
-    let data = read_to_string(pathname).expect("unable to open file");
+    let data = read_input(pathname).expect("unable to open file");
     data.chars().collect()
 }
 
@@ -127,7 +447,7 @@ pub fn numbers<T: AsRef<Path>, U: FromStr>(pathname: T, sep: char) -> Vec<Vec<U>
 where
     <U as FromStr>::Err: Debug,
 {
-    let data = read_to_string(pathname).expect("unable to open file");
+    let data = read_input(pathname).expect("unable to open file");
     let mut result = Vec::new();
 
     for line in data.split('\n') {
@@ -147,7 +467,7 @@ pub fn read_line_of<T: AsRef<Path>, U: FromStr>(pathname: T, sep: char) -> Vec<U
 where
     <U as FromStr>::Err: Debug,
 {
-    let line = read_to_string(pathname)
+    let line = read_input(pathname)
         .expect("unable to open file")
         .trim()
         .to_owned();
@@ -157,7 +477,7 @@ where
 }
 
 pub fn read_lines<T: AsRef<Path>>(pathname: T) -> Vec<String> {
-    read_to_string(pathname)
+    read_input(pathname)
         .expect("unable to open file")
         .split('\n')
         .filter(|s| !s.trim().is_empty())
@@ -178,7 +498,7 @@ pub fn read_num_records<T: AsRef<Path>, U: FromStr>(pathname: T) -> Vec<Vec<U>>
 where
     <U as FromStr>::Err: Debug,
 {
-    read_to_string(pathname)
+    read_input(pathname)
         .expect("unable to open file")
         .split("\n\n")
         .filter(|s| !s.is_empty())
@@ -192,7 +512,7 @@ where
 }
 
 pub fn read_single_line<T: AsRef<Path>>(pathname: T) -> Vec<char> {
-    read_to_string(pathname)
+    read_input(pathname)
         .expect("unable to open file")
         .chars()
         .filter(|&ch| ch != '\n')
@@ -204,7 +524,7 @@ where
     <U as FromStr>::Err: Debug,
 {
     let mut result = Vec::new();
-    for line in read_to_string(pathname)
+    for line in read_input(pathname)
         .expect("Unable to find {pathname:?}")
         .lines()
     {
@@ -212,3 +532,34 @@ where
     }
     result
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn catches_panics_on_many_threads() {
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    for j in 0..50 {
+                        assert_eq!(catch_panic(|| i * j), Some(i * j));
+                        assert_eq!(catch_panic(|| panic!("{i} {j}")), None::<()>);
+                        let nested = catch_panic(|| {
+                            assert_eq!(catch_panic(|| panic!("inner")), None::<()>);
+                            panic!("outer")
+                        });
+                        assert_eq!(nested, None::<()>);
+                        let line = line!() + 1;
+                        let location = catch_panic_location(|| panic!("{i} {j}"));
+                        assert_eq!(location, Err::<(), _>(format!("src/lib.rs:{line}")));
+                    }
+                    CATCHING.get()
+                })
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), 0);
+        }
+    }
+}
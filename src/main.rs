@@ -1,8 +1,18 @@
-use clap::Parser;
+use std::fs::{read_to_string, write};
+use std::path::PathBuf;
+use std::time::Duration;
 
-use aoc_2023_rust::Selector;
+use clap::{Parser, Subcommand, ValueEnum};
 
-use crate::solutions::run;
+use aoc_2023_rust::{
+    check_in_child, decrypt_file, encrypt_file, input_key, minimize, page_path, read_input,
+    render_page, render_standings, save_examples, CrossCheckConfig, Failure, Leaderboard, Program,
+    Rng, Selector, Source, CROSSCHECK_CONFIG, KEY_VAR, REGRESSIONS_DIR,
+};
+
+use crate::solutions::{
+    create, days, run, run_crosscheck, run_differential, run_inputs, run_profile, run_verify_input,
+};
 
 mod solutions;
 
@@ -14,6 +24,8 @@ mod solutions;
     about
 )]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
     /// Execute all solutions
     #[arg(short, long, default_value_t = false)]
     pub all: bool,
@@ -24,9 +36,450 @@ pub struct Args {
     #[arg(short, long, default_value_t = true)]
     pub last: bool,
 }
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run days against every dayNN*.txt input found under a directory
+    Run {
+        /// Directory searched recursively for inputs
+        #[arg(long)]
+        inputs: PathBuf,
+        /// Days to run, all days by default
+        #[arg(short, long, value_delimiter = ' ', num_args = 1..)]
+        day: Vec<u8>,
+    },
+    /// Check that the alternative implementations of a day give the same answers
+    Differential {
+        /// Directory searched recursively for inputs
+        #[arg(long, default_value = "input")]
+        inputs: PathBuf,
+        /// Days to check, all days by default
+        #[arg(short, long, value_delimiter = ' ', num_args = 1..)]
+        day: Vec<u8>,
+        /// Number of generated inputs to check too
+        #[arg(long, default_value_t = 10)]
+        generated: u64,
+        /// Size of the generated inputs
+        #[arg(long, default_value_t = 10)]
+        size: usize,
+    },
+    /// Write a random input of a day, reproducible from its seed
+    Generate {
+        /// Day between 1..25
+        #[arg(short, long)]
+        day: u8,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        /// Scales the number of lines or the grid of the input
+        #[arg(long, default_value_t = 10)]
+        size: usize,
+        /// File the input is written to, standard output by default
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Shrink an input that makes a day fail into a small reproducer
+    Minimize {
+        /// Day between 1..25
+        #[arg(short, long)]
+        day: u8,
+        /// Failing input
+        input: PathBuf,
+        /// What counts as the failure
+        #[arg(long, value_enum, default_value_t = FailureKind::Panic)]
+        failure: FailureKind,
+        /// Part that fails, any part by default for a panic
+        #[arg(long)]
+        part: Option<u8>,
+        /// Answer that counts as the failure with `--failure answer`
+        #[arg(long)]
+        answer: Option<String>,
+        /// Seconds after which a run counts as not failing
+        #[arg(long, default_value_t = 10)]
+        timeout: u64,
+        /// File the reproducer is written to, a regression input by default
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Print how an input fails, run by `minimize` for each candidate
+    #[command(hide = true)]
+    Signature {
+        #[arg(short, long)]
+        day: u8,
+        #[arg(long, value_enum)]
+        failure: FailureKind,
+        #[arg(long)]
+        part: Option<u8>,
+        #[arg(long)]
+        answer: Option<String>,
+        input: PathBuf,
+    },
+    /// Compare the answers with those of external solution programs
+    Crosscheck {
+        /// Days to check, all days by default
+        #[arg(short, long, value_delimiter = ' ', num_args = 1..)]
+        day: Vec<u8>,
+        /// JSON file listing the programs
+        #[arg(long, default_value = CROSSCHECK_CONFIG)]
+        config: PathBuf,
+        /// Only run the program of this name
+        #[arg(long)]
+        program: Option<String>,
+        /// Directory searched recursively for inputs, the days' own inputs by default
+        #[arg(long)]
+        inputs: Option<PathBuf>,
+    },
+    /// Fit how the runtime of a part grows with the size of its input
+    Profile {
+        /// Day between 1..25
+        #[arg(short, long)]
+        day: u8,
+        #[arg(short, long, default_value_t = 1)]
+        part: u8,
+        /// Tile the day's own input instead of using its generator
+        #[arg(long)]
+        tiled: bool,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        /// Size of the first input, or the times the input is tiled
+        #[arg(long, default_value_t = 4)]
+        start: usize,
+        /// Factor the size grows by on each step
+        #[arg(long, default_value_t = 2)]
+        factor: usize,
+        #[arg(long, default_value_t = 6)]
+        steps: u32,
+        /// Runs of each input, of which the median is taken
+        #[arg(long, default_value_t = 3)]
+        runs: usize,
+        /// Seconds after which no larger inputs are tried
+        #[arg(long, default_value_t = 10)]
+        max_time: u64,
+    },
+    /// Check the assumptions days make about their input
+    VerifyInput {
+        /// Days to check, all days by default
+        #[arg(short, long, value_delimiter = ' ', num_args = 1..)]
+        day: Vec<u8>,
+        /// Directory searched recursively for inputs, the days' own inputs by default
+        #[arg(long)]
+        inputs: Option<PathBuf>,
+    },
+    /// Extract example inputs and answers from a saved puzzle page
+    Examples {
+        /// Day between 1..25
+        #[arg(short, long)]
+        day: u8,
+        /// Saved puzzle page, input/pages/dayNN.html by default
+        #[arg(long)]
+        page: Option<PathBuf>,
+        /// Directory the examples and their answers are written to
+        #[arg(long, default_value = "input")]
+        out: PathBuf,
+    },
+    /// Show the saved puzzle description of a day
+    Show {
+        /// Day between 1..25
+        #[arg(short, long)]
+        day: u8,
+        /// Width the text is wrapped to
+        #[arg(short, long, default_value_t = 80)]
+        width: usize,
+    },
+    /// Show standings and star times of a private leaderboard JSON export
+    Leaderboard {
+        /// Exported leaderboard JSON file
+        file: PathBuf,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = Format::Pretty)]
+        format: Format,
+    },
+    /// Encrypt inputs into dayNN.txt.enc files, all puzzle inputs by default
+    Encrypt {
+        /// Plaintext inputs to encrypt
+        files: Vec<PathBuf>,
+    },
+    /// Decrypt dayNN.txt.enc files back to plaintext, all puzzle inputs by default
+    Decrypt {
+        /// Inputs to decrypt, given by their plaintext path
+        files: Vec<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum FailureKind {
+    /// Parsing or a part panics
+    Panic,
+    /// A part gives the answer given with `--answer`
+    Answer,
+    /// Alternative implementations of a part disagree
+    Mismatch,
+}
+
+impl FailureKind {
+    fn failure(self, part: Option<u8>, answer: Option<String>) -> Option<Failure> {
+        match (self, answer) {
+            (FailureKind::Panic, _) => Some(Failure::Panic { part }),
+            (FailureKind::Mismatch, _) => Some(Failure::Mismatch),
+            (FailureKind::Answer, Some(answer)) => Some(Failure::Answer {
+                part: part.unwrap_or(1),
+                answer,
+            }),
+            (FailureKind::Answer, None) => None,
+        }
+    }
+}
+
+/// The arguments of the `signature` command that checks a candidate of
+/// `minimize` in a child process, without the path of the candidate.
+fn signature_args(
+    day: u8,
+    kind: FailureKind,
+    part: Option<u8>,
+    answer: Option<&str>,
+) -> Vec<String> {
+    let kind = kind.to_possible_value().unwrap();
+    let mut args = vec![
+        "signature".to_string(),
+        "--day".to_string(),
+        day.to_string(),
+        "--failure".to_string(),
+        kind.get_name().to_string(),
+    ];
+    if let Some(part) = part {
+        args.extend(["--part".to_string(), part.to_string()]);
+    }
+    if let Some(answer) = answer {
+        args.extend(["--answer".to_string(), answer.to_string()]);
+    }
+    args
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Format {
+    Pretty,
+    Json,
+}
+
+fn crypt_files(files: Vec<PathBuf>, encrypt: bool) {
+    let Some(key) = input_key() else {
+        eprintln!("Set {KEY_VAR} or write the key to .aoc-key");
+        return;
+    };
+    let files = if files.is_empty() {
+        days()
+            .iter()
+            .map(|d| PathBuf::from(format!("input/day{d:02}.txt")))
+            .collect()
+    } else {
+        files
+    };
+
+    for file in files {
+        let result = if encrypt {
+            encrypt_file(&file, &key)
+        } else {
+            decrypt_file(&file, &key)
+        };
+        match result {
+            Ok(written) => println!("{}", written.display()),
+            Err(e) => eprintln!("{}: {e}", file.display()),
+        }
+    }
+}
+
 fn main() {
     let args = Args::parse();
 
+    if let Some(command) = args.command {
+        match command {
+            Command::Run { inputs, day } => {
+                let day = if day.is_empty() { days() } else { day };
+                run_inputs(&inputs, &day);
+            }
+            Command::Differential {
+                inputs,
+                day,
+                generated,
+                size,
+            } => {
+                let day = if day.is_empty() { days() } else { day };
+                run_differential(&inputs, &day, generated, size);
+            }
+            Command::Generate {
+                day,
+                seed,
+                size,
+                out,
+            } => {
+                let input = create(day).and_then(|s| s.generate(&mut Rng::new(seed), size));
+                match (input, out) {
+                    (None, _) => eprintln!("Day {day} has no generator"),
+                    (Some(input), None) => print!("{input}"),
+                    (Some(input), Some(out)) => {
+                        if let Err(e) = write(&out, input) {
+                            eprintln!("Unable to write {}: {e}", out.display());
+                        }
+                    }
+                }
+            }
+            Command::Minimize {
+                day,
+                input,
+                failure,
+                part,
+                answer,
+                timeout,
+                out,
+            } => {
+                let args = signature_args(day, failure, part, answer.as_deref());
+                let Some(failure) = failure.failure(part, answer) else {
+                    eprintln!("Give the failing answer with --answer");
+                    return;
+                };
+                if create(day).is_none() {
+                    eprintln!("Day {day} is not solved yet");
+                    return;
+                }
+                let data = match read_input(&input) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        eprintln!("Unable to read {}: {e}", input.display());
+                        return;
+                    }
+                };
+                let out = out.unwrap_or_else(|| {
+                    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+                    let name = format!(
+                        "day{day:02}-{}-min.txt",
+                        stem.trim_start_matches(&format!("day{day:02}-"))
+                    );
+                    PathBuf::from(REGRESSIONS_DIR).join(name)
+                });
+                let program = match std::env::current_exe() {
+                    Ok(program) => program,
+                    Err(e) => {
+                        eprintln!("Unable to find the running program: {e}");
+                        return;
+                    }
+                };
+                let timeout = Duration::from_secs(timeout);
+                let check = |candidate: &str| check_in_child(&program, &args, candidate, timeout);
+                match minimize(&data, check) {
+                    Some(reproducer) => {
+                        println!(
+                            "{} lines -> {} lines",
+                            data.lines().count(),
+                            reproducer.lines().count()
+                        );
+                        match write(&out, reproducer) {
+                            Ok(()) => println!("{}", out.display()),
+                            Err(e) => eprintln!("Unable to write {}: {e}", out.display()),
+                        }
+                    }
+                    None => eprintln!("{} does not fail as {failure:?}", input.display()),
+                }
+            }
+            Command::Signature {
+                day,
+                failure,
+                part,
+                answer,
+                input,
+            } => {
+                if let (Some(failure), Some(_)) = (failure.failure(part, answer), create(day)) {
+                    failure.print_signature(&|| create(day).unwrap(), &input);
+                }
+            }
+            Command::Crosscheck {
+                day,
+                config,
+                program,
+                inputs,
+            } => {
+                let programs = match read_to_string(&config)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| CrossCheckConfig::from_json(&json))
+                {
+                    Ok(config) => config.programs,
+                    Err(e) => {
+                        eprintln!("Unable to read {}: {e}", config.display());
+                        return;
+                    }
+                };
+                let programs: Vec<Program> = programs
+                    .into_iter()
+                    .filter(|p| program.as_ref().is_none_or(|name| &p.name == name))
+                    .collect();
+                let day = if day.is_empty() { days() } else { day };
+                if !run_crosscheck(&programs, inputs.as_deref(), &day) {
+                    std::process::exit(1);
+                }
+            }
+            Command::Profile {
+                day,
+                part,
+                tiled,
+                seed,
+                start,
+                factor,
+                steps,
+                runs,
+                max_time,
+            } => {
+                let source = if tiled {
+                    let path = format!("input/day{day:02}.txt");
+                    match read_input(&path) {
+                        Ok(input) => Source::Tiled(input),
+                        Err(e) => {
+                            eprintln!("Unable to read {path}: {e}");
+                            return;
+                        }
+                    }
+                } else {
+                    Source::Generated { seed }
+                };
+                let sizes: Vec<usize> = (0..steps).map(|i| start * factor.pow(i)).collect();
+                let max_time = Duration::from_secs(max_time);
+                run_profile(day, part, &source, &sizes, runs, max_time);
+            }
+            Command::VerifyInput { day, inputs } => {
+                let day = if day.is_empty() { days() } else { day };
+                if !run_verify_input(inputs.as_deref(), &day) {
+                    std::process::exit(1);
+                }
+            }
+            Command::Examples { day, page, out } => {
+                let page = page.unwrap_or_else(|| page_path(day as usize));
+                match save_examples(day as usize, &page, &out) {
+                    Ok(files) => files.iter().for_each(|f| println!("{}", f.display())),
+                    Err(e) => eprintln!("Unable to read examples from {}: {e}", page.display()),
+                }
+            }
+            Command::Show { day, width } => {
+                let page = page_path(day as usize);
+                match read_to_string(&page) {
+                    Ok(html) => print!("{}", render_page(&html, width)),
+                    Err(e) => eprintln!("No saved puzzle page at {}: {e}", page.display()),
+                }
+            }
+            Command::Leaderboard { file, format } => {
+                let leaderboard = read_to_string(&file)
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| Leaderboard::from_json(&json).map_err(|e| e.to_string()));
+                match (leaderboard, format) {
+                    (Ok(l), Format::Pretty) => print!("{}", render_standings(&l.standings())),
+                    (Ok(l), Format::Json) => {
+                        println!("{}", serde_json::to_string_pretty(&l.standings()).unwrap())
+                    }
+                    (Err(e), _) => eprintln!("Unable to read {}: {e}", file.display()),
+                }
+            }
+            Command::Encrypt { files } => crypt_files(files, true),
+            Command::Decrypt { files } => crypt_files(files, false),
+        }
+        return;
+    }
+
     if let Some(day) = args.day {
         if !day.is_empty() && matches!(day[0], 1..=25) {
             run(Selector::One(day));
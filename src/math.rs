@@ -0,0 +1,304 @@
+use std::ops::RangeInclusive;
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, `None` if it does not fit in a `u64`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+pub fn gcd_all(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// The least common multiple of all values, 1 for none, and `None` if it
+/// does not fit in a `u64`.
+pub fn lcm_all(values: impl IntoIterator<Item = u64>) -> Option<u64> {
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// `(g, x, y)` with `g` the non-negative gcd of `a` and `b`, and
+/// `a * x + b * y == g`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a * b % modulus` without overflowing.
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+/// `base` to the power of `exponent`, modulo `modulus`.
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "modulus 0");
+    let mut result = 1 % modulus;
+    let mut base = base % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// The `x` in `0..modulus` with `a * x % modulus == 1`, `None` unless `a` and
+/// `modulus` are coprime.
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    let (g, x, _) = extended_gcd(a as i128, modulus as i128);
+    (g == 1).then(|| x.rem_euclid(modulus as i128) as u64)
+}
+
+/// The `x` satisfying every `x % modulus == residue`, as `(x, period)` with
+/// `x` the smallest and `period` the lcm of the moduli, so that the
+/// solutions are `x + k * period`. The moduli need not be coprime. `None`
+/// when the congruences contradict each other, a modulus is 0, or the
+/// period does not fit in a `u64`.
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, period), (residue, modulus)| {
+            if modulus == 0 {
+                return None;
+            }
+            let residue = residue % modulus;
+            // Solves x + period * k == residue modulo `modulus` for k.
+            let g = gcd(period, modulus);
+            let difference = residue as i128 - (x % modulus) as i128;
+            if difference % g as i128 != 0 {
+                return None;
+            }
+            let step = modulus / g;
+            let difference = (difference / g as i128).rem_euclid(step as i128) as u64;
+            let k = mul_mod(difference, mod_inverse(period / g % step, step)?, step);
+            // `x + period * k` is below the new period, so it fits if that does.
+            let new_period = (period / g).checked_mul(modulus)?;
+            Some((x + period * k, new_period))
+        })
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from a power of two above the root only decreases
+    // until it reaches it.
+    let bits = 128 - n.leading_zeros();
+    let mut root = 1u128 << bits.div_ceil(2);
+    loop {
+        let next = (root + n / root) / 2;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt(n: u64) -> u64 {
+    isqrt128(n as u128) as u64
+}
+
+fn quadratic(a: i128, b: i128, c: i128, x: i128) -> i128 {
+    (a * x + b) * x + c
+}
+
+/// The integers `x` with `a * x² + b * x + c == 0`, in increasing order.
+/// `b² - 4ac` must fit in an `i128`.
+pub fn quadratic_roots(a: i64, b: i64, c: i64) -> Vec<i64> {
+    let (a, b, c) = (a as i128, b as i128, c as i128);
+    let candidates = if a == 0 {
+        match b {
+            0 => Vec::new(),
+            _ => vec![-c / b],
+        }
+    } else {
+        let discriminant = b * b - 4 * a * c;
+        if discriminant < 0 {
+            return Vec::new();
+        }
+        let root = isqrt128(discriminant as u128) as i128;
+        vec![(-b - root) / (2 * a), (-b + root) / (2 * a)]
+    };
+    let mut roots: Vec<i64> = candidates
+        .into_iter()
+        .filter(|x| quadratic(a, b, c, *x) == 0)
+        .map(|x| x as i64)
+        .collect();
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// The integers `x` with `a * x² + b * x + c < 0`, for a positive `a`, so
+/// those strictly between the real roots. `b² - 4ac` must fit in an `i128`.
+pub fn quadratic_below_zero(a: i64, b: i64, c: i64) -> Option<RangeInclusive<i64>> {
+    assert!(a > 0, "the quadratic must open upwards, not with a = {a}");
+    let (a, b, c) = (a as i128, b as i128, c as i128);
+    let discriminant = b * b - 4 * a * c;
+    if discriminant <= 0 {
+        return None;
+    }
+    // The integer root rounds the real roots by less than one, which the
+    // loops correct exactly.
+    let root = isqrt128(discriminant as u128) as i128;
+    let (mut low, mut high) = ((-b - root) / (2 * a), (-b + root) / (2 * a));
+    while quadratic(a, b, c, low) >= 0 && low <= high {
+        low += 1;
+    }
+    while quadratic(a, b, c, low - 1) < 0 {
+        low -= 1;
+    }
+    while quadratic(a, b, c, high) >= 0 && high >= low {
+        high -= 1;
+    }
+    while quadratic(a, b, c, high + 1) < 0 {
+        high += 1;
+    }
+    (low <= high).then_some(low as i64..=high as i64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Rng;
+
+    const CASES: usize = 2000;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm_all([2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(lcm_all([u64::MAX, u64::MAX - 1]), None);
+        assert_eq!(gcd_all([12, 18, 8]), 2);
+
+        let mut rng = Rng::new(46);
+        for _ in 0..CASES {
+            let (a, b) = (rng.range(1, 1 << 20), rng.range(1, 1 << 20));
+            let (g, m) = (gcd(a, b), lcm(a, b).unwrap());
+            assert!(a % g == 0 && b % g == 0 && m % a == 0 && m % b == 0);
+            assert_eq!(g * m, a * b);
+            let (g, x, y) = extended_gcd(a as i128, -(b as i128));
+            assert_eq!(g, gcd(a, b) as i128);
+            assert_eq!(a as i128 * x - b as i128 * y, g);
+        }
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 8), None);
+
+        let mut rng = Rng::new(46);
+        for _ in 0..CASES {
+            let modulus = rng.range(1, u64::MAX);
+            let (base, exponent) = (rng.next_u64(), rng.range(0, 64));
+            let expected = (0..exponent).fold(1 % modulus, |p, _| mul_mod(p, base, modulus));
+            assert_eq!(mod_pow(base, exponent, modulus), expected);
+            // Fermat: a^(p-1) == 1 modulo the prime 2^61 - 1.
+            let prime = (1 << 61) - 1;
+            let a = rng.range(1, prime);
+            assert_eq!(mod_pow(a, prime - 1, prime), 1);
+            let inverse = mod_inverse(a, prime).unwrap();
+            assert_eq!(mul_mod(a, inverse, prime), 1);
+        }
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(0, u64::MAX), (1, u64::MAX - 1)]), None);
+
+        let mut rng = Rng::new(46);
+        for _ in 0..CASES {
+            // Built from a known solution, so that one always exists.
+            let x = rng.next_u64() >> 4;
+            let moduli: Vec<u64> = (0..rng.range(1, 5)).map(|_| rng.range(1, 1000)).collect();
+            let (solution, period) = crt(moduli.iter().map(|m| (x % m, *m))).unwrap();
+            assert_eq!(Some(period), lcm_all(moduli.iter().copied()));
+            assert!(solution < period);
+            assert_eq!(solution, x % period);
+        }
+    }
+
+    #[test]
+    fn integer_square_roots() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt128(u128::MAX), u64::MAX as u128);
+
+        let mut rng = Rng::new(46);
+        for _ in 0..CASES {
+            let n = rng.next_u64() >> rng.below(64);
+            let r = isqrt(n) as u128;
+            assert!(r * r <= n as u128 && (r + 1) * (r + 1) > n as u128, "{n}");
+            let n = (n as u128) << 64 | rng.next_u64() as u128;
+            let r = isqrt128(n);
+            assert!(r * r <= n && (r + 1).checked_mul(r + 1).is_none_or(|s| s > n));
+        }
+    }
+
+    #[test]
+    fn quadratics() {
+        assert_eq!(quadratic_roots(1, -5, 6), vec![2, 3]);
+        assert_eq!(quadratic_roots(1, -4, 4), vec![2]);
+        assert_eq!(quadratic_roots(2, -3, 1), vec![1]);
+        assert!(quadratic_roots(1, 0, 1).is_empty());
+        assert_eq!(quadratic_roots(0, 2, -8), vec![4]);
+        // Speeds beating a record of 9 in a race of 7: x(7 - x) > 9.
+        assert_eq!(quadratic_below_zero(1, -7, 9), Some(2..=5));
+        assert_eq!(quadratic_below_zero(1, -30, 200), Some(11..=19));
+        assert_eq!(quadratic_below_zero(1, -4, 4), None);
+
+        let mut rng = Rng::new(46);
+        for _ in 0..CASES {
+            let a = rng.range(1, 20) as i64;
+            let b = rng.range(0, 400) as i64 - 200;
+            let c = rng.range(0, 4000) as i64 - 2000;
+            let f = |x: i64| a * x * x + b * x + c;
+            let brute: Vec<i64> = (-1000..=1000).filter(|x| f(*x) == 0).collect();
+            assert_eq!(quadratic_roots(a, b, c), brute);
+            let below: Vec<i64> = (-1000..=1000).filter(|x| f(*x) < 0).collect();
+            let range = quadratic_below_zero(a, b, c);
+            assert_eq!(
+                range.map(Iterator::collect::<Vec<i64>>).unwrap_or_default(),
+                below
+            );
+        }
+    }
+}
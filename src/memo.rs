@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// Set to print the statistics of each memo when it is dropped, in debug
+/// builds.
+pub const MEMO_STATS: &str = "MEMO_STATS";
+
+/// Cached results of a recursive function, keyed by its arguments. Borrowed
+/// inputs such as slices stay out of the key: the function is keyed on the
+/// indices into them, and a memo is used for one input only.
+///
+/// ```ignore
+/// fn paths(memo: &mut Memo<usize, u64>, grid: &[u8], i: usize) -> u64 {
+///     memo.get_or_compute(i, |memo| match i {
+///         0 => 1,
+///         _ => paths(memo, grid, i - 1) + paths(memo, grid, i / 2),
+///     })
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    capacity: Option<usize>,
+    stats: MemoStats,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lookups = self.hits + self.misses;
+        let rate = match lookups {
+            0 => 0.0,
+            _ => 100.0 * self.hits as f64 / lookups as f64,
+        };
+        write!(
+            f,
+            "{} hits, {} misses ({rate:.1}% hit rate)",
+            self.hits, self.misses
+        )
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            capacity: None,
+            stats: MemoStats::default(),
+        }
+    }
+
+    /// A memo holding at most `capacity` results. Once it is full, new
+    /// results are computed every time.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            cache: HashMap::new(),
+            capacity: Some(capacity),
+            stats: MemoStats::default(),
+        }
+    }
+
+    /// The cached result for `key`, or else the result of `compute`, which
+    /// is given the memo for its recursive calls.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key).cloned() {
+            self.count(|stats| stats.hits += 1);
+            return value;
+        }
+        self.count(|stats| stats.misses += 1);
+        let value = compute(self);
+        if self.capacity.is_none_or(|c| self.cache.len() < c) {
+            self.cache.insert(key, value.clone());
+        }
+        value
+    }
+
+    /// Hits and misses are only counted in debug builds.
+    fn count(&mut self, update: impl FnOnce(&mut MemoStats)) {
+        if cfg!(debug_assertions) {
+            update(&mut self.stats);
+        }
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forgets all results, e.g. before reusing the memo for another input.
+    /// The statistics keep counting across inputs.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        if cfg!(debug_assertions) && std::env::var_os(MEMO_STATS).is_some() {
+            eprintln!("Memo: {}", self.stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Ways to climb `steps` stairs one or two at a time, counting calls.
+    fn climb(memo: &mut Memo<usize, u64>, calls: &mut u64, steps: usize) -> u64 {
+        *calls += 1;
+        memo.get_or_compute(steps, |memo| match steps {
+            0 | 1 => 1,
+            _ => climb(memo, calls, steps - 1) + climb(memo, calls, steps - 2),
+        })
+    }
+
+    /// Keys on indices into borrowed slices: ways to build `target` from the
+    /// words in order.
+    fn spellings(memo: &mut Memo<usize, u64>, words: &[&str], target: &str, at: usize) -> u64 {
+        memo.get_or_compute(at, |memo| {
+            if at == target.len() {
+                return 1;
+            }
+            words
+                .iter()
+                .filter(|w| target[at..].starts_with(**w))
+                .map(|w| spellings(memo, words, target, at + w.len()))
+                .sum()
+        })
+    }
+
+    #[test]
+    fn caches_recursive_calls() {
+        let (mut memo, mut calls) = (Memo::new(), 0);
+        assert_eq!(climb(&mut memo, &mut calls, 80), 37889062373143906);
+        assert_eq!(calls, 2 * 80 - 1);
+        assert_eq!(memo.len(), 81);
+        if cfg!(debug_assertions) {
+            assert_eq!(
+                memo.stats(),
+                MemoStats {
+                    hits: 78,
+                    misses: 81
+                }
+            );
+            assert_eq!(
+                memo.stats().to_string(),
+                "78 hits, 81 misses (49.1% hit rate)"
+            );
+        }
+
+        let words = ["a", "ab", "b", "ba", "bab"];
+        let mut memo = Memo::new();
+        assert_eq!(spellings(&mut memo, &words, "ababab", 0), 18);
+        let stats = memo.stats();
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(spellings(&mut memo, &words, "abc", 0), 0);
+        if cfg!(debug_assertions) {
+            assert!(memo.stats().misses > stats.misses);
+        }
+    }
+
+    #[test]
+    fn capacity_bound() {
+        let (mut memo, mut calls) = (Memo::with_capacity(10), 0);
+        assert_eq!(climb(&mut memo, &mut calls, 20), 10946);
+        assert_eq!(memo.len(), 10);
+        // The small cached results cut the recursion short, but not as much.
+        assert!(calls > 2 * 20 - 1, "{calls}");
+        assert!(calls < 2 * 10946, "{calls}");
+    }
+}
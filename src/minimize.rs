@@ -0,0 +1,292 @@
+use std::fs::{read_to_string, remove_file, File};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::fuzzing::write_temp_input;
+use crate::{catch_panic_location, find_mismatch, Runner, PANICKED};
+
+/// Start of the line on which `Failure::print_signature` prints the
+/// signature of a failing input.
+pub const SIGNATURE_PREFIX: &str = "signature: ";
+
+/// What makes an input fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// Parsing or a part panics, or only the given part when there is one.
+    Panic { part: Option<u8> },
+    /// A part gives the given answer.
+    Answer { part: u8, answer: String },
+    /// The implementations of a part disagree, without any of them panicking.
+    Mismatch,
+}
+
+impl Failure {
+    /// Describes how the input at `path` fails, so that a smaller input only
+    /// counts when it fails the same way: a panic at the same source line, or
+    /// a mismatch on the same part.
+    fn signature<F>(&self, new_solution: &F, path: &Path) -> Option<String>
+    where
+        F: Fn() -> Box<dyn Runner>,
+    {
+        let pathname = path.to_string_lossy();
+        match self {
+            Failure::Panic { part } => {
+                let mut solution = new_solution();
+                catch_panic_location(|| {
+                    solution.parse(Some(&pathname));
+                    if part.unwrap_or(1) == 1 {
+                        solution.part1();
+                    }
+                    if part.unwrap_or(2) == 2 {
+                        solution.part2();
+                    }
+                })
+                .err()
+            }
+            Failure::Answer { part, answer } => {
+                let mut solution = new_solution();
+                let output = catch_panic_location(|| {
+                    solution.parse(Some(&pathname));
+                    match part {
+                        1 => solution.part1(),
+                        _ => solution.part2(),
+                    }
+                })
+                .ok()?;
+                (output.join("\n") == *answer).then(String::new)
+            }
+            Failure::Mismatch => find_mismatch(new_solution, &[path.to_path_buf()])
+                .filter(|m| m.answers.iter().all(|(_, a)| a != PANICKED))
+                .map(|m| format!("part {}", m.part)),
+        }
+    }
+
+    /// Checks `input` in this process, for solutions that always finish.
+    pub fn check<F>(&self, new_solution: &F, input: &str) -> Option<String>
+    where
+        F: Fn() -> Box<dyn Runner>,
+    {
+        let path = write_temp_input(input.as_bytes());
+        let signature = self.signature(new_solution, &path);
+        let _ = remove_file(&path);
+        signature
+    }
+
+    /// Prints the signature of the input at `path` for `check_in_child`.
+    pub fn print_signature<F>(&self, new_solution: &F, path: &Path)
+    where
+        F: Fn() -> Box<dyn Runner>,
+    {
+        if let Some(signature) = self.signature(new_solution, path) {
+            println!("{SIGNATURE_PREFIX}{signature}");
+        }
+    }
+}
+
+/// Checks `input` in a child process, `program` run with `args` and the path
+/// of the input, which prints its signature with `Failure::print_signature`.
+/// Inputs cut down at random may never finish, so the child is killed after
+/// `timeout` and the input counts as not failing.
+pub fn check_in_child(
+    program: &Path,
+    args: &[String],
+    input: &str,
+    timeout: Duration,
+) -> Option<String> {
+    let path = write_temp_input(input.as_bytes());
+    let out = path.with_extension("out");
+    let signature = run_child(program, args, &path, &out, timeout);
+    let _ = remove_file(&path);
+    let _ = remove_file(&out);
+    signature
+}
+
+fn run_child(
+    program: &Path,
+    args: &[String],
+    path: &Path,
+    out: &Path,
+    timeout: Duration,
+) -> Option<String> {
+    // The output goes to a file, as a pipe that is not read while waiting
+    // could fill up and block the child.
+    let mut child = Command::new(program)
+        .args(args)
+        .arg(path)
+        .stdout(File::create(out).ok()?)
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let start = Instant::now();
+    while child.try_wait().ok()?.is_none() {
+        if start.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    read_to_string(out)
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix(SIGNATURE_PREFIX))
+        .map(str::to_string)
+}
+
+/// Shrinks an input by removing blank line separated blocks, lines and grid
+/// columns for as long as it keeps failing the same way. `check` gives the
+/// signature of how an input fails, such as `Failure::check` or
+/// `check_in_child`, and `None` if it does not. Returns `None` if `input`
+/// does not fail in the first place.
+pub fn minimize(input: &str, mut check: impl FnMut(&str) -> Option<String>) -> Option<String> {
+    let target = check(input)?;
+    let mut fails = |candidate: &str| check(candidate).as_ref() == Some(&target);
+    let newline = if input.ends_with('\n') { "\n" } else { "" };
+
+    let mut current = input.trim_end_matches('\n').to_string();
+    loop {
+        let before = current.len();
+
+        let blocks: Vec<String> = current.split("\n\n").map(str::to_string).collect();
+        let blocks = ddmin(blocks, &mut |b| fails(&(b.join("\n\n") + newline)));
+        current = blocks.join("\n\n");
+
+        let lines: Vec<String> = current.lines().map(str::to_string).collect();
+        let lines = ddmin(lines, &mut |l| fails(&(l.join("\n") + newline)));
+        current = lines.join("\n");
+
+        let rows: Vec<Vec<char>> = current.lines().map(|l| l.chars().collect()).collect();
+        let width = rows.first().map_or(0, |r| r.len());
+        if width > 1 && rows.iter().all(|r| r.len() == width) {
+            let with_columns = |columns: &[usize]| {
+                rows.iter()
+                    .map(|r| columns.iter().map(|c| r[*c]).collect::<String>())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            let columns = ddmin((0..width).collect(), &mut |c| {
+                fails(&(with_columns(c) + newline))
+            });
+            current = with_columns(&columns);
+        }
+
+        if current.len() == before {
+            return Some(current + newline);
+        }
+    }
+}
+
+/// Removes chunks of `items`, halving the chunk size whenever none of them
+/// can go, for as long as `fails` holds for what is left.
+fn ddmin<T: Clone>(mut items: Vec<T>, fails: &mut dyn FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut chunks = 2;
+    while items.len() >= 2 {
+        let size = items.len().div_ceil(chunks);
+        let reduced = (0..items.len()).step_by(size).find_map(|start| {
+            let mut candidate = items[..start].to_vec();
+            candidate.extend_from_slice(&items[(start + size).min(items.len())..]);
+            fails(&candidate).then_some(candidate)
+        });
+        match reduced {
+            Some(candidate) => {
+                items = candidate;
+                chunks = (chunks - 1).max(2);
+            }
+            None if chunks >= items.len() => break,
+            None => chunks = (chunks * 2).min(items.len()),
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::read_to_string;
+
+    use super::*;
+    use crate::output;
+
+    /// Counts the lines starting with `x` in part 1 and panics on lines `a`
+    /// and `b` together, or on any `#` in part 2.
+    #[derive(Default)]
+    struct Picky {
+        lines: Vec<String>,
+    }
+
+    impl Runner for Picky {
+        fn name(&self) -> (usize, usize) {
+            (2023, 0)
+        }
+
+        fn parse(&mut self, input: Option<&str>) {
+            let input = read_to_string(input.unwrap()).unwrap();
+            self.lines = input.lines().map(str::to_string).collect();
+        }
+
+        fn part1(&mut self) -> Vec<String> {
+            let has = |line: &str| self.lines.iter().any(|l| l == line);
+            assert!(!(has("a") && has("b")));
+            output(self.lines.iter().filter(|l| l.starts_with('x')).count())
+        }
+
+        fn part2(&mut self) -> Vec<String> {
+            assert!(!self.lines.iter().any(|l| l.contains('#')));
+            output(0)
+        }
+    }
+
+    fn picky() -> Box<dyn Runner> {
+        Box::<Picky>::default()
+    }
+
+    fn minimize_picky(input: &str, failure: &Failure) -> Option<String> {
+        minimize(input, |candidate| failure.check(&picky, candidate))
+    }
+
+    #[test]
+    fn minimizes_lines_and_columns() {
+        let lines: String = (0..20).map(|i| format!("{i}\n")).collect();
+        let input = lines.replace("4\n", "a\n").replace("17\n", "b\n");
+        assert_eq!(
+            minimize_picky(&input, &Failure::Panic { part: Some(1) }),
+            Some("a\nb\n".to_string())
+        );
+
+        let grid = "....\n..#.\n....\n";
+        assert_eq!(
+            minimize_picky(grid, &Failure::Panic { part: None }),
+            Some("#\n".to_string())
+        );
+
+        let answer = Failure::Answer {
+            part: 1,
+            answer: "2".to_string(),
+        };
+        assert_eq!(
+            minimize_picky("x1\ny\nx2\nz\n", &answer),
+            Some("x\nx\n".to_string())
+        );
+        assert_eq!(minimize_picky("x\n", &answer), None);
+    }
+
+    #[test]
+    fn kills_children_that_run_too_long() {
+        let sh = Path::new("sh");
+        let timeout = Duration::from_secs(5);
+        let script = |s: &str| vec!["-c".to_string(), s.to_string(), "sh".to_string()];
+        assert_eq!(
+            check_in_child(sh, &script("echo 'signature: a.rs:3'"), "", timeout),
+            Some("a.rs:3".to_string())
+        );
+        assert_eq!(check_in_child(sh, &script("echo ok"), "", timeout), None);
+
+        let start = Instant::now();
+        let sleep = script("sleep 30; echo 'signature: late'");
+        assert_eq!(
+            check_in_child(sh, &sleep, "", Duration::from_millis(100)),
+            None
+        );
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
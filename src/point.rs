@@ -1,9 +1,8 @@
-use std::fmt::Debug;
-use std::ops::{Add, AddAssign, Sub};
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 use std::str::FromStr;
 
-#[derive(Debug)]
-enum ParsePointError {}
+use crate::{Dir8, Direction};
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Point<T> {
@@ -11,7 +10,7 @@ pub struct Point<T> {
     pub y: T,
 }
 
-impl std::fmt::Display for Point<i32> {
+impl<T: Display> Display for Point<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Point (x: {}, y: {})", self.x, self.y)
     }
@@ -21,6 +20,15 @@ impl<T> Point<T> {
     pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
+
+    /// The point with both coordinates converted to `U`, `None` if either
+    /// does not fit, e.g. a negative coordinate into an unsigned type.
+    pub fn cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+        Some(Point {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+        })
+    }
 }
 
 impl<T: AddAssign> AddAssign for Point<T> {
@@ -30,6 +38,13 @@ impl<T: AddAssign> AddAssign for Point<T> {
     }
 }
 
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
 impl<T: Add<Output = T>> Add for Point<T> {
     type Output = Self;
 
@@ -41,24 +56,46 @@ impl<T: Add<Output = T>> Add for Point<T> {
     }
 }
 
-impl<T: FromStr> Point<T> {
-    pub fn parse(s: &str, separator: &str) -> Self
-    where
-        <T as FromStr>::Err: Debug,
-    {
-        let mut value = s.split(separator);
-        let x = value.next().unwrap().parse::<T>().unwrap();
-        let y = value.next().unwrap().parse::<T>().unwrap();
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
 
-        Self { x, y }
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
     }
 }
 
-impl<T: FromStr> FromStr for Point<T> {
-    type Err = String;
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
 
-    fn from_str(p: &str) -> Result<Self, Self::Err> {
-        let (x, y) = p.split_once(", ").ok_or("input file corrupt")?;
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+}
+
+impl<T: FromStr> Point<T> {
+    /// Parses `x` and `y` separated by `separator`, ignoring the whitespace
+    /// around both.
+    pub fn parse(s: &str, separator: &str) -> Result<Self, String> {
+        let (x, y) = s
+            .split_once(separator)
+            .ok_or_else(|| format!("no '{separator}' in point '{s}'"))?;
         let (x, y) = (x.trim(), y.trim());
 
         Ok(Self {
@@ -68,6 +105,26 @@ impl<T: FromStr> FromStr for Point<T> {
     }
 }
 
+/// Parses `x` and `y` separated by a comma, as in `1,2` or `1, 2`, or else
+/// by whitespace, as in `1 2`.
+impl<T: FromStr> FromStr for Point<T> {
+    type Err = String;
+
+    fn from_str(p: &str) -> Result<Self, Self::Err> {
+        let p = p.trim();
+        match p.find(',') {
+            Some(_) => Self::parse(p, ","),
+            None => {
+                let separator = p
+                    .find(char::is_whitespace)
+                    .map(|i| &p[i..=i])
+                    .ok_or("input file corrupt")?;
+                Self::parse(p, separator)
+            }
+        }
+    }
+}
+
 impl<T: Copy + Ord + Add<T, Output = T> + Sub<T, Output = T>> Point<T> {
     pub fn min(&self, other: &Self) -> Self {
         Self {
@@ -83,15 +140,81 @@ impl<T: Copy + Ord + Add<T, Output = T> + Sub<T, Output = T>> Point<T> {
         }
     }
 
-    pub fn dist(&self, x: T, y: T) -> T
-    where
-        <T as Sub>::Output: Add<T>,
-    {
-        let delta_x = if self.x > x { self.x - x } else { x - self.x };
-        let delta_y = if self.y > y { self.y - y } else { y - self.y };
+    /// Distances along each axis, without overflowing unsigned types.
+    fn axis_distances(&self, other: &Self) -> (T, T) {
+        let delta_x = if self.x > other.x {
+            self.x - other.x
+        } else {
+            other.x - self.x
+        };
+        let delta_y = if self.y > other.y {
+            self.y - other.y
+        } else {
+            other.y - self.y
+        };
+        (delta_x, delta_y)
+    }
 
+    /// Steps between the points when moving along the axes.
+    pub fn manhattan(&self, other: &Self) -> T {
+        let (delta_x, delta_y) = self.axis_distances(other);
         delta_x + delta_y
     }
+
+    /// Steps between the points when diagonal steps are allowed too.
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let (delta_x, delta_y) = self.axis_distances(other);
+        Ord::max(delta_x, delta_y)
+    }
+}
+
+impl<T: Copy + TryInto<i64> + TryFrom<i64>> Point<T> {
+    /// The points sharing a side with this one: north, east, south and west,
+    /// with `y` growing south. Points that do not fit in `T` are left out,
+    /// such as those left of `x = 0` for an unsigned point.
+    pub fn neighbors4(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| point.checked_add(d.delta()))
+    }
+
+    /// The points sharing a side or a corner with this one, clockwise from
+    /// north. Points that do not fit in `T` are left out.
+    pub fn neighbors8(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |d| point.checked_add(d.delta()))
+    }
+
+    fn checked_add(self, offset: Point<i64>) -> Option<Self> {
+        let coordinate =
+            |value: T, delta: i64| T::try_from(value.try_into().ok()?.checked_add(delta)?).ok();
+        Some(Self {
+            x: coordinate(self.x, offset.x)?,
+            y: coordinate(self.y, offset.y)?,
+        })
+    }
+
+    /// `self + offset` if it lies within `0..width` and `0..height`, so that
+    /// unsigned points can be moved by signed offsets.
+    pub fn checked_add_within<U>(self, offset: Point<U>, width: T, height: T) -> Option<Self>
+    where
+        U: TryInto<i64>,
+    {
+        let coordinate = |value: T, delta: U, limit: T| {
+            let sum = value.try_into().ok()?.checked_add(delta.try_into().ok()?)?;
+            (0..limit.try_into().ok()?)
+                .contains(&sum)
+                .then(|| T::try_from(sum).ok())
+                .flatten()
+        };
+        Some(Self {
+            x: coordinate(self.x, offset.x, width)?,
+            y: coordinate(self.y, offset.y, height)?,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -103,4 +226,101 @@ mod test {
         let p = "1, 2".parse::<Point<usize>>().unwrap();
         assert_eq!(Point { x: 1usize, y: 2 }, p);
     }
+
+    #[test]
+    fn any_separator() {
+        assert_eq!("3,-4".parse(), Ok(Point::new(3, -4)));
+        assert_eq!(" 3  4 ".parse(), Ok(Point::new(3u8, 4)));
+        assert_eq!(Point::parse("7 x 8", "x"), Ok(Point::new(7, 8)));
+        assert_eq!(
+            Point::<u32>::parse("7;8", ","),
+            Err("no ',' in point '7;8'".to_string())
+        );
+        assert_eq!(
+            "1, -2".parse::<Point<u32>>(),
+            Err("y value '-2' corrupt".to_string())
+        );
+        assert!("12".parse::<Point<i32>>().is_err());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Point::new(1, 2), Point::new(4, -2));
+        assert_eq!(a + b, Point::new(5, 0));
+        assert_eq!(a - b, Point::new(-3, 4));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(b * 3, Point::new(12, -6));
+        let mut c = a;
+        c += b;
+        c -= Point::new(1, 1);
+        assert_eq!(c, Point::new(4, -1));
+        assert_eq!(a.min(&b), Point::new(1, -2));
+        assert_eq!(a.max(&b), Point::new(4, 2));
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point::new(1u32, 7), Point::new(4, 2));
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(b.manhattan(&a), 8);
+        assert_eq!(a.chebyshev(&b), 5);
+        assert_eq!(a.chebyshev(&a), 0);
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Point::new(3i32, 4).cast::<u8>(), Some(Point::new(3u8, 4)));
+        assert_eq!(Point::new(-1i32, 4).cast::<usize>(), None);
+        assert_eq!(Point::new(300u32, 4).cast::<i8>(), None);
+        assert_eq!(
+            Point::new(2usize, 5).cast::<i64>(),
+            Some(Point::new(2i64, 5))
+        );
+    }
+
+    #[test]
+    fn neighbors() {
+        let p = Point::new(0i32, 0);
+        assert_eq!(
+            p.neighbors4().collect::<Vec<_>>(),
+            [
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        let around: Vec<_> = p.neighbors8().collect();
+        assert!(around.iter().all(|n| n.chebyshev(&p) == 1));
+        assert_eq!(around.iter().filter(|n| n.manhattan(&p) == 2).count(), 4);
+
+        let corner = Point::new(0u32, 0);
+        assert_eq!(
+            corner.neighbors4().collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(Point::new(3usize, 0).neighbors8().count(), 5);
+        assert_eq!(Point::new(u8::MAX, 1).neighbors4().count(), 3);
+    }
+
+    #[test]
+    fn checked_add_within_bounds() {
+        let p = Point::new(0usize, 2);
+        assert_eq!(
+            p.checked_add_within(Point::new(1, -1), 3, 3),
+            Some(Point::new(1, 1))
+        );
+        assert_eq!(p.checked_add_within(Point::new(-1, 0), 3, 3), None);
+        assert_eq!(p.checked_add_within(Point::new(0, 1), 3, 3), None);
+        assert_eq!(
+            Point::new(-2i32, 0).checked_add_within(Point::new(2i8, 0), 1, 1),
+            Some(Point::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Point::new(1, -2).to_string(), "Point (x: 1, y: -2)\n");
+        assert_eq!(Point::new(1.5, 2.0).to_string(), "Point (x: 1.5, y: 2)\n");
+    }
 }
@@ -0,0 +1,228 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Point3 (x: {}, y: {}, z: {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The point with all coordinates converted to `U`, `None` if any does
+    /// not fit.
+    pub fn cast<U: TryFrom<T>>(self) -> Option<Point3<U>> {
+        Some(Point3 {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+            z: U::try_from(self.z).ok()?,
+        })
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+}
+
+impl<T: FromStr> Point3<T> {
+    /// Parses `x`, `y` and `z` separated by `separator`, ignoring the
+    /// whitespace around each.
+    pub fn parse(s: &str, separator: &str) -> Result<Self, String> {
+        let values: Vec<&str> = s.split(separator).map(str::trim).collect();
+        let [x, y, z] = values[..] else {
+            return Err(format!(
+                "point '{s}' has {} values separated by '{separator}', not 3",
+                values.len()
+            ));
+        };
+
+        Ok(Self {
+            x: x.parse().map_err(|_| format!("x value '{x}' corrupt"))?,
+            y: y.parse().map_err(|_| format!("y value '{y}' corrupt"))?,
+            z: z.parse().map_err(|_| format!("z value '{z}' corrupt"))?,
+        })
+    }
+
+    /// Parses a position and a velocity, as in `19, 13, 30 @ -2, 1, -2`.
+    pub fn parse_with_velocity(s: &str) -> Result<(Self, Self), String> {
+        let (position, velocity) = s
+            .split_once('@')
+            .ok_or_else(|| format!("no '@' in '{s}'"))?;
+        Ok((position.parse()?, velocity.parse()?))
+    }
+}
+
+/// Parses `x`, `y` and `z` separated by commas, as in `1,2,3` or `1, 2, 3`,
+/// or else by whitespace, as in `1 2 3`.
+impl<T: FromStr> FromStr for Point3<T> {
+    type Err = String;
+
+    fn from_str(p: &str) -> Result<Self, Self::Err> {
+        let p = p.trim();
+        if p.contains(',') {
+            return Self::parse(p, ",");
+        }
+        let values: Vec<&str> = p.split_whitespace().collect();
+        Self::parse(&values.join(" "), " ")
+    }
+}
+
+impl<T: Copy + Ord + Add<T, Output = T> + Sub<T, Output = T>> Point3<T> {
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            x: Ord::min(self.x, other.x),
+            y: Ord::min(self.y, other.y),
+            z: Ord::min(self.z, other.z),
+        }
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            x: Ord::max(self.x, other.x),
+            y: Ord::max(self.y, other.y),
+            z: Ord::max(self.z, other.z),
+        }
+    }
+
+    /// Distances along each axis, without overflowing unsigned types.
+    fn axis_distances(&self, other: &Self) -> [T; 3] {
+        let distance = |a: T, b: T| if a > b { a - b } else { b - a };
+        [
+            distance(self.x, other.x),
+            distance(self.y, other.y),
+            distance(self.z, other.z),
+        ]
+    }
+
+    /// Steps between the points when moving along the axes.
+    pub fn manhattan(&self, other: &Self) -> T {
+        let [x, y, z] = self.axis_distances(other);
+        x + y + z
+    }
+
+    /// Steps between the points when diagonal steps are allowed too.
+    pub fn chebyshev(&self, other: &Self) -> T {
+        let [x, y, z] = self.axis_distances(other);
+        Ord::max(Ord::max(x, y), z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        assert_eq!("1,0,1".parse(), Ok(Point3::new(1, 0, 1)));
+        assert_eq!(" 19, 13, 30 ".parse(), Ok(Point3::new(19u32, 13, 30)));
+        assert_eq!("4  5 6".parse(), Ok(Point3::new(4, 5, 6)));
+        assert_eq!(Point3::parse("1~2~3", "~"), Ok(Point3::new(1, 2, 3)));
+        assert_eq!(
+            "1,2".parse::<Point3<i32>>(),
+            Err("point '1,2' has 2 values separated by ',', not 3".to_string())
+        );
+        assert_eq!(
+            "1, 2, x".parse::<Point3<i32>>(),
+            Err("z value 'x' corrupt".to_string())
+        );
+        assert_eq!(
+            Point3::parse_with_velocity("19, 13, 30 @ -2,  1, -2"),
+            Ok((Point3::new(19i64, 13, 30), Point3::new(-2, 1, -2)))
+        );
+        assert!(Point3::<i64>::parse_with_velocity("19, 13, 30").is_err());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Point3::new(1, 2, 3), Point3::new(4, -2, 0));
+        assert_eq!(a + b, Point3::new(5, 0, 3));
+        assert_eq!(a - b, Point3::new(-3, 4, 3));
+        assert_eq!(-a, Point3::new(-1, -2, -3));
+        assert_eq!(b * 2, Point3::new(8, -4, 0));
+        let mut c = a;
+        c += b;
+        c -= Point3::new(1, 1, 1);
+        assert_eq!(c, Point3::new(4, -1, 2));
+        assert_eq!(a.min(&b), Point3::new(1, -2, 0));
+        assert_eq!(a.max(&b), Point3::new(4, 2, 3));
+        assert_eq!(a.manhattan(&b), 10);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(
+            Point3::new(1u8, 2, 3).cast::<i64>(),
+            Some(Point3::new(1, 2, 3))
+        );
+        assert_eq!(Point3::new(-1i32, 2, 3).cast::<u8>(), None);
+        assert_eq!(a.to_string(), "Point3 (x: 1, y: 2, z: 3)");
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt::{Display, Write};
+use std::fs::remove_file;
+use std::time::Duration;
+
+use crate::fuzzing::write_temp_input;
+use crate::{measure_phases, Budget, Rng, Runner};
+
+/// Complexity classes the timings of a part are fitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+    Exponential,
+}
+
+impl Complexity {
+    pub const ALL: [Complexity; 5] = [
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+        Complexity::Exponential,
+    ];
+
+    /// Natural logarithm of the growth function at `n`. The base of the
+    /// exponential class is fitted instead, see `fit`.
+    fn ln_growth(&self, n: f64) -> f64 {
+        match self {
+            Complexity::Linear => n.ln(),
+            Complexity::Linearithmic => n.ln() + n.max(2.0).log2().ln(),
+            Complexity::Quadratic => 2.0 * n.ln(),
+            Complexity::Cubic => 3.0 * n.ln(),
+            Complexity::Exponential => n,
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Complexity::Linear => write!(f, "O(n)"),
+            Complexity::Linearithmic => write!(f, "O(n log n)"),
+            Complexity::Quadratic => write!(f, "O(n²)"),
+            Complexity::Cubic => write!(f, "O(n³)"),
+            Complexity::Exponential => write!(f, "O(2ⁿ)"),
+        }
+    }
+}
+
+/// Median time of a part on an input of `n` bytes, made at `size`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub size: usize,
+    pub n: usize,
+    pub time: Duration,
+}
+
+/// Fits `time = c * f(n)` for every complexity class, and `time = c * bⁿ`
+/// for the exponential one, by least squares over the logarithms of the
+/// times, so that every point weighs the same whatever its size. Returns the
+/// classes with their mean squared error, best first.
+pub fn fit(samples: &[Sample]) -> Vec<(Complexity, f64)> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|s| (s.n.max(1) as f64, s.time.as_secs_f64().max(1e-9).ln()))
+        .collect();
+    let mean = |values: Vec<f64>| values.iter().sum::<f64>() / values.len() as f64;
+    let mut fits: Vec<(Complexity, f64)> = Complexity::ALL
+        .iter()
+        .map(|class| {
+            // ln(time) = ln(c) + slope * ln(f(n)), where only the exponential
+            // class fits its slope, ln(b), as it grows with n itself.
+            let xs: Vec<f64> = points.iter().map(|(n, _)| class.ln_growth(*n)).collect();
+            let x_mean = mean(xs.clone());
+            let t_mean = mean(points.iter().map(|p| p.1).collect());
+            let slope = match class {
+                Complexity::Exponential => {
+                    let covariance = mean(
+                        xs.iter()
+                            .zip(&points)
+                            .map(|(x, (_, t))| (x - x_mean) * (t - t_mean))
+                            .collect(),
+                    );
+                    let variance = mean(xs.iter().map(|x| (x - x_mean).powi(2)).collect());
+                    covariance / variance.max(f64::MIN_POSITIVE)
+                }
+                _ => 1.0,
+            };
+            let error = mean(
+                xs.iter()
+                    .zip(&points)
+                    .map(|(x, (_, t))| (t - t_mean - slope * (x - x_mean)).powi(2))
+                    .collect(),
+            );
+            (*class, error)
+        })
+        .collect();
+    fits.sort_by(|a, b| a.1.total_cmp(&b.1));
+    fits
+}
+
+/// The input repeated `times` times: a grid is tiled `times` times across
+/// and down, any other input is repeated line by line, which suits the days
+/// with one record per line.
+pub fn tile(input: &str, times: usize) -> String {
+    let lines: Vec<&str> = input.lines().collect();
+    let width = lines.first().map_or(0, |l| l.len());
+    let grid = lines.len() > 1
+        && lines
+            .iter()
+            .all(|l| l.len() == width && !l.contains(char::is_whitespace));
+    if grid {
+        let row = |line: &str| line.repeat(times) + "\n";
+        lines
+            .iter()
+            .map(|l| row(l))
+            .collect::<String>()
+            .repeat(times)
+    } else {
+        (input.trim_end_matches('\n').to_string() + "\n").repeat(times)
+    }
+}
+
+/// Where the inputs of growing size come from.
+pub enum Source {
+    /// The day's generator at each size.
+    Generated { seed: u64 },
+    /// The given input tiled as many times as each size.
+    Tiled(String),
+}
+
+/// Times `part` on inputs of the geometrically growing `sizes`, stopping
+/// after the first input that takes longer than `max_time`.
+pub fn profile<F>(
+    new_solution: F,
+    part: u8,
+    source: &Source,
+    sizes: &[usize],
+    runs: usize,
+    max_time: Duration,
+) -> Vec<Sample>
+where
+    F: Fn() -> Box<dyn Runner>,
+{
+    let mut budget = Budget::default();
+    budget.phases[part as usize] = Some(Duration::ZERO);
+    let mut samples = Vec::new();
+    for size in sizes {
+        let input = match source {
+            Source::Generated { seed } => {
+                match new_solution().generate(&mut Rng::new(*seed), *size) {
+                    Some(input) => input,
+                    None => break,
+                }
+            }
+            Source::Tiled(input) => tile(input, *size),
+        };
+        let path = write_temp_input(input.as_bytes());
+        let medians = measure_phases(&new_solution, Some(&path.to_string_lossy()), runs, &budget);
+        let _ = remove_file(&path);
+        let Some(time) = medians[part as usize] else {
+            break;
+        };
+        samples.push(Sample {
+            size: *size,
+            n: input.len(),
+            time,
+        });
+        if time > max_time {
+            break;
+        }
+    }
+    samples
+}
+
+/// A table of the samples followed by the best fitting complexity class.
+pub fn profile_report(samples: &[Sample]) -> String {
+    let mut report = format!("{:>8}  {:>10}  {:>12}\n", "size", "bytes", "time");
+    for sample in samples {
+        let _ = writeln!(
+            report,
+            "{:>8}  {:>10}  {:>12}",
+            sample.size,
+            sample.n,
+            format!("{:.2?}", sample.time)
+        );
+    }
+    if samples.len() < 3 {
+        report.push_str("Too few points to fit a complexity class\n");
+        return report;
+    }
+    let fits = fit(samples);
+    let _ = writeln!(report, "Best fit: {}", fits[0].0);
+    for (class, error) in fits {
+        let _ = writeln!(report, "\t{:12} error {error:.4}", class.to_string());
+    }
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn samples(time: impl Fn(f64) -> f64) -> Vec<Sample> {
+        (0..6)
+            .map(|i| {
+                let n = 8 << i;
+                Sample {
+                    size: n,
+                    n,
+                    time: Duration::from_secs_f64(time(n as f64)),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_complexity_classes() {
+        let best = |time: fn(f64) -> f64| fit(&samples(time))[0].0;
+        assert_eq!(best(|n| 3e-6 * n), Complexity::Linear);
+        assert_eq!(best(|n| 1e-6 * n * n.log2()), Complexity::Linearithmic);
+        assert_eq!(best(|n| 2e-7 * n * n), Complexity::Quadratic);
+        assert_eq!(best(|n| 1e-9 * n * n * n), Complexity::Cubic);
+        assert_eq!(best(|n| 1e-9 * 1.1f64.powf(n)), Complexity::Exponential);
+    }
+
+    #[test]
+    fn tiles_grids_and_lines() {
+        assert_eq!(tile("ab\ncd\n", 2), "abab\ncdcd\nabab\ncdcd\n");
+        assert_eq!(tile("1 2\n3 4", 2), "1 2\n3 4\n1 2\n3 4\n");
+    }
+}
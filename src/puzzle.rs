@@ -0,0 +1,384 @@
+use std::fs::{read_to_string, write};
+use std::path::{Path, PathBuf};
+
+use colored::*;
+
+use crate::html::{tokenize, Token};
+use crate::AnswerStore;
+
+pub const PAGES_DIR: &str = "input/pages";
+
+/// Path of the saved puzzle page of `day`, e.g. `input/pages/day01.html`.
+pub fn page_path(day: usize) -> PathBuf {
+    PathBuf::from(PAGES_DIR).join(format!("day{day:02}.html"))
+}
+
+/// Name of the nth example input file of `day`: `day01-test.txt`,
+/// `day01-test2.txt` and so on.
+pub fn example_file_name(day: usize, index: usize) -> String {
+    match index {
+        0 => format!("day{day:02}-test.txt"),
+        n => format!("day{day:02}-test{}.txt", n + 1),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleAnswer {
+    pub part: u8,
+    pub example: usize,
+    pub answer: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Examples {
+    pub inputs: Vec<String>,
+    pub answers: Vec<ExampleAnswer>,
+}
+
+/// Finds the example inputs and their answers on a puzzle page.
+///
+/// Each `<article class="day-desc">` is one part of the puzzle. The answer of
+/// a part is the last emphasized code (`<code><em>142</em></code>`) of its
+/// article, and it belongs to the `<pre><code>` block closest before it. Only
+/// blocks that some answer belongs to are kept as example inputs.
+pub fn extract_examples(html: &str) -> Examples {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut answers: Vec<(u8, usize, String)> = Vec::new();
+
+    let mut part = 0;
+    let mut in_article = false;
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut in_em = false;
+    let mut text = String::new();
+    let mut emphasized = None;
+
+    for token in tokenize(html) {
+        if token.is_start("article")
+            && token
+                .attribute("class")
+                .is_some_and(|c| c.contains("day-desc"))
+        {
+            part += 1;
+            in_article = true;
+            emphasized = None;
+            continue;
+        }
+        if !in_article {
+            continue;
+        }
+
+        match &token {
+            Token::End(name) if name == "article" => {
+                if let Some((Some(block), answer)) = emphasized.take() {
+                    answers.push((part, block, answer));
+                }
+                in_article = false;
+            }
+            Token::Start { name, .. } if name == "pre" => in_pre = true,
+            Token::End(name) if name == "pre" => in_pre = false,
+            Token::Start { name, .. } if name == "code" => {
+                in_code = true;
+                text.clear();
+            }
+            Token::End(name) if name == "code" => {
+                if in_pre {
+                    blocks.push(text.clone());
+                }
+                in_code = false;
+            }
+            Token::Start { name, .. } if name == "em" => in_em = true,
+            Token::End(name) if name == "em" => in_em = false,
+            Token::Text(t) if in_code => {
+                text.push_str(t);
+                if in_em && !in_pre {
+                    emphasized = Some((blocks.len().checked_sub(1), t.trim().to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut examples = Examples::default();
+    for (part, block, answer) in answers {
+        let example = match examples.inputs.iter().position(|i| *i == blocks[block]) {
+            Some(i) => i,
+            None => {
+                examples.inputs.push(blocks[block].clone());
+                examples.inputs.len() - 1
+            }
+        };
+        examples.answers.push(ExampleAnswer {
+            part,
+            example,
+            answer,
+        });
+    }
+    examples
+}
+
+/// Writes the examples found on a saved puzzle page into `dir` and records
+/// their answers in the answer store of `dir`. Returns the written files.
+pub fn save_examples<T: AsRef<Path>, U: AsRef<Path>>(
+    day: usize,
+    page: T,
+    dir: U,
+) -> std::io::Result<Vec<PathBuf>> {
+    let examples = extract_examples(&read_to_string(page)?);
+    let mut answers = AnswerStore::load(&dir);
+    let mut written = Vec::new();
+
+    for (i, input) in examples.inputs.iter().enumerate() {
+        let path = dir.as_ref().join(example_file_name(day, i));
+        write(&path, input)?;
+        written.push(path);
+    }
+    for answer in &examples.answers {
+        answers.insert(
+            &example_file_name(day, answer.example),
+            answer.part,
+            &answer.answer,
+        );
+    }
+    answers.save()?;
+
+    Ok(written)
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Style {
+    em: bool,
+    code: bool,
+    link: bool,
+}
+
+impl Style {
+    fn apply(&self, text: &str) -> ColoredString {
+        let mut s = text.normal();
+        if self.code {
+            s = s.cyan();
+        }
+        if self.em {
+            s = s.bright_white().bold();
+        }
+        if self.link {
+            s = s.underline();
+        }
+        s
+    }
+}
+
+/// Renders the puzzle articles of a saved puzzle page as colored text
+/// wrapped to `width` columns.
+pub fn render_page(html: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut words: Vec<Vec<(String, Style)>> = Vec::new();
+    let mut style = Style::default();
+    let mut articles = 0;
+    let mut in_article = false;
+    let mut in_pre = false;
+    let mut pre = String::new();
+    let mut indent = "";
+    let mut bullet = "";
+    let mut space_before = false;
+
+    for token in tokenize(html) {
+        if token.is_start("article")
+            && token
+                .attribute("class")
+                .is_some_and(|c| c.contains("day-desc"))
+        {
+            articles += 1;
+            in_article = true;
+            continue;
+        }
+        if !in_article {
+            continue;
+        }
+
+        match &token {
+            Token::End(name) if name == "article" => {
+                flush_paragraph(&mut out, &mut words, width, indent, bullet);
+                in_article = false;
+            }
+            Token::Start { name, .. } if name == "h2" => style.em = true,
+            Token::End(name) if name == "h2" => {
+                let title: String = words.drain(..).flatten().map(|(t, _)| t + " ").collect();
+                out.push_str(&format!("{}\n\n", title.trim().green().bold()));
+                style.em = false;
+            }
+            Token::Start { name, .. } if name == "pre" => in_pre = true,
+            Token::End(name) if name == "pre" => {
+                for line in pre.trim_end_matches('\n').lines() {
+                    out.push_str(&format!("    {}\n", line.cyan()));
+                }
+                out.push('\n');
+                pre.clear();
+                in_pre = false;
+            }
+            Token::Start { name, .. } if name == "ul" => indent = "  ",
+            Token::End(name) if name == "ul" => {
+                indent = "";
+                out.push('\n');
+            }
+            Token::Start { name, .. } if name == "li" => bullet = "- ",
+            Token::End(name) if name == "li" => {
+                flush_line_item(&mut out, &mut words, width, indent, bullet);
+                bullet = "";
+            }
+            Token::End(name) if name == "p" => {
+                flush_paragraph(&mut out, &mut words, width, indent, bullet)
+            }
+            Token::Start { name, .. } if name == "em" => style.em = true,
+            Token::End(name) if name == "em" => style.em = false,
+            Token::Start { name, .. } if name == "code" => style.code = true,
+            Token::End(name) if name == "code" => style.code = false,
+            Token::Start { name, .. } if name == "a" => style.link = true,
+            Token::End(name) if name == "a" => style.link = false,
+            Token::Text(t) if in_pre => pre.push_str(t),
+            Token::Text(t) => {
+                for (i, piece) in t.split(char::is_whitespace).enumerate() {
+                    space_before |= i > 0;
+                    if piece.is_empty() {
+                        continue;
+                    }
+                    match words.last_mut() {
+                        Some(word) if !space_before => word.push((piece.to_string(), style)),
+                        _ => words.push(vec![(piece.to_string(), style)]),
+                    }
+                    space_before = false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if articles < 2 {
+        out.push_str(&format!("{}\n", "Part two is not saved yet.".dimmed()));
+    }
+    out
+}
+
+fn flush_line_item(
+    out: &mut String,
+    words: &mut Vec<Vec<(String, Style)>>,
+    width: usize,
+    indent: &str,
+    bullet: &str,
+) {
+    let first = format!("{indent}{bullet}");
+    let rest = " ".repeat(first.len());
+    wrap(out, words, width, &first, &rest);
+}
+
+fn flush_paragraph(
+    out: &mut String,
+    words: &mut Vec<Vec<(String, Style)>>,
+    width: usize,
+    indent: &str,
+    bullet: &str,
+) {
+    if words.is_empty() {
+        return;
+    }
+    if !bullet.is_empty() {
+        return flush_line_item(out, words, width, indent, bullet);
+    }
+    wrap(out, words, width, indent, indent);
+    out.push('\n');
+}
+
+fn wrap(
+    out: &mut String,
+    words: &mut Vec<Vec<(String, Style)>>,
+    width: usize,
+    first: &str,
+    rest: &str,
+) {
+    let mut column = 0;
+    for word in words.drain(..) {
+        let len: usize = word.iter().map(|(t, _)| t.chars().count()).sum();
+        if column == 0 {
+            out.push_str(first);
+            column = first.len();
+        } else if column + 1 + len > width {
+            out.push('\n');
+            out.push_str(rest);
+            column = rest.len();
+        } else {
+            out.push(' ');
+            column += 1;
+        }
+        for (text, style) in word {
+            out.push_str(&style.apply(&text).to_string());
+        }
+        column += len;
+    }
+    if column > 0 {
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>In this example, the calibration values are <code>12</code> and <code>38</code>.
+Adding these together produces <code><em>50</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54081</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+</code></pre>
+<p>Adding these together produces <em><code>29</code></em>.</p>
+<pre><code>not an example
+</code></pre>
+</article>
+</main>"#;
+
+    #[test]
+    fn render_wraps_and_lists() {
+        let page = "<article class=\"day-desc\"><h2>--- Day 1 ---</h2>\
+                    <p>The <em>newly</em>-improved <code>calibration</code> document \
+                    consists of lines of text.</p><ul><li>one two three four</li></ul>\
+                    <pre><code>1abc2\n</code></pre></article>";
+        assert_eq!(
+            crate::without_colors(|| render_page(page, 24)),
+            "--- Day 1 ---\n\n\
+             The newly-improved\n\
+             calibration document\n\
+             consists of lines of\n\
+             text.\n\n  \
+             - one two three four\n\n    \
+             1abc2\n\n\
+             Part two is not saved yet.\n"
+        );
+    }
+
+    #[test]
+    fn examples_from_page() {
+        let examples = extract_examples(PAGE);
+        assert_eq!(examples.inputs, vec!["1abc2\npqr3stu8vwx\n", "two1nine\n"]);
+        assert_eq!(
+            examples.answers,
+            vec![
+                ExampleAnswer {
+                    part: 1,
+                    example: 0,
+                    answer: "50".to_string()
+                },
+                ExampleAnswer {
+                    part: 2,
+                    example: 1,
+                    answer: "29".to_string()
+                },
+            ]
+        );
+    }
+}
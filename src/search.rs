@@ -0,0 +1,271 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found: the distance to every state it reached, how it
+/// reached them, and the goal it stopped at if any.
+#[derive(Debug, Clone)]
+pub struct Search<S, C> {
+    distances: HashMap<S, C>,
+    predecessors: HashMap<S, S>,
+    goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Search<S, C> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// The cost of the cheapest way found from a start to `state`.
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<S, C> {
+        &self.distances
+    }
+
+    /// The first goal state the search reached.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|goal| self.distance(goal))
+    }
+
+    /// The states from a start to `state`, both included.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        self.distances.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The states from a start to the goal, both included.
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.path(self.goal.as_ref()?)
+    }
+
+    /// The states reached, all those reachable from the starts unless the
+    /// search stopped at a goal.
+    pub fn reachable(&self) -> impl Iterator<Item = &S> {
+        self.distances.keys()
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.distances.contains_key(state)
+    }
+}
+
+/// Breadth-first search from all of `starts` at once, counting one step per
+/// neighbor, until a state satisfies `goal`. Pass `|_| false` as `goal` to
+/// reach every state.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Search<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+    while let Some(state) = queue.pop_front() {
+        if goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        let distance = search.distances[&state];
+        for next in neighbors(&state) {
+            if !search.distances.contains_key(&next) {
+                search.distances.insert(next.clone(), distance + 1);
+                search.predecessors.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Cheapest paths from all of `starts` at once, where `neighbors` gives each
+/// next state with the non-negative cost of the step to it, until a state
+/// satisfies `goal`. Pass `|_| false` as `goal` to reach every state.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), goal)
+}
+
+/// Dijkstra's search guided by `heuristic`, an estimate of the cost from a
+/// state to the nearest goal. The distance to the goal is the cheapest as
+/// long as the estimate never exceeds the real cost.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search::new();
+    // The heap orders indexes into `states`, so that states need not be
+    // ordered themselves.
+    let mut states = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if !search.distances.contains_key(&start) {
+            search.distances.insert(start.clone(), C::default());
+            heap.push(Reverse((heuristic(&start), C::default(), states.len())));
+            states.push(start);
+        }
+    }
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let state = states[index].clone();
+        if search.distances[&state] < cost {
+            // Reached more cheaply since it was queued.
+            continue;
+        }
+        if goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if search.distances.get(&next).is_none_or(|d| next_cost < *d) {
+                search.distances.insert(next.clone(), next_cost);
+                search.predecessors.insert(next.clone(), state.clone());
+                heap.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    states.len(),
+                )));
+                states.push(next);
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Direction, Grid, Point};
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn open(grid: &Grid<char>, p: &Point<i32>) -> Vec<Point<i32>> {
+        p.neighbors4()
+            .filter(|n| grid.get(*n).is_some_and(|c| *c != '#'))
+            .collect()
+    }
+
+    #[test]
+    fn bfs_paths() {
+        let grid = Grid::parse(MAZE, |c| c);
+        let start = grid.find(|c| *c == 'S').unwrap();
+        let end = grid.find(|c| *c == 'E').unwrap();
+
+        let search = bfs([start], |p| open(&grid, p), |p| *p == end);
+        assert_eq!(search.goal(), Some(&end));
+        assert_eq!(search.goal_distance(), Some(15));
+        let path = search.goal_path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!((path[0], path[15]), (start, end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+
+        let all = bfs([start], |p| open(&grid, p), |_| false);
+        assert_eq!(all.goal(), None);
+        assert_eq!(all.reachable().count(), 27);
+        assert!(!all.contains(&Point::new(3, 0)));
+
+        // From both corners, the farthest tile is the dead end below the
+        // middle, 10 steps from S along the bottom row.
+        let both = bfs([start, end], |p| open(&grid, p), |_| false);
+        assert_eq!(both.distance(&end), Some(0));
+        assert_eq!(both.distance(&Point::new(7, 0)), Some(4));
+        assert_eq!(both.distances().values().max(), Some(&10));
+        assert_eq!(both.distance(&Point::new(5, 3)), Some(10));
+        assert_eq!(both.path(&start), Some(vec![start]));
+    }
+
+    #[test]
+    fn weighted_states() {
+        // Each step costs the digit entered, and a crucible may move at most
+        // three times in one direction, and never turn back.
+        let grid = Grid::parse(
+            "2413432311323\n3215453535623\n3255245654254\n3446585845452\n\
+             4546657867536\n1438598798454\n4457876987766\n3637877979653\n\
+             4654967986887\n4564679986453\n1224686865563\n2546548887735\n\
+             4322674655533",
+            |c| c.to_digit(10).unwrap(),
+        );
+        let end = Point::new(12, 12);
+        type State = (Point<i32>, Direction, u8);
+        let moves = |(p, d, run): &State| {
+            [d.turn_left(), *d, d.turn_right()]
+                .into_iter()
+                .filter(|next| next != d || *run < 3)
+                .filter_map(|next| {
+                    let q = *p + next.delta();
+                    let run = if next == *d { run + 1 } else { 1 };
+                    grid.get(q).map(|cost| ((q, next, run), *cost))
+                })
+                .collect::<Vec<_>>()
+        };
+        let starts = [
+            (Point::new(0, 0), Direction::East, 0),
+            (Point::new(0, 0), Direction::South, 0),
+        ];
+
+        let search = dijkstra(starts, moves, |(p, _, _)| *p == end);
+        assert_eq!(search.goal_distance(), Some(102));
+        let path = search.goal_path().unwrap();
+        assert!(starts.contains(&path[0]));
+        let cost: u32 = path[1..].iter().map(|(p, _, _)| grid[*p]).sum();
+        assert_eq!(cost, 102);
+
+        let guided = astar(
+            starts,
+            moves,
+            |(p, _, _)| p.manhattan(&end) as u32,
+            |(p, _, _)| *p == end,
+        );
+        assert_eq!(guided.goal_distance(), Some(102));
+        assert!(guided.reachable().count() <= search.reachable().count());
+
+        let unreachable = dijkstra(starts, moves, |(p, _, _)| *p == Point::new(13, 0));
+        assert_eq!(unreachable.goal(), None);
+        assert_eq!(unreachable.goal_path(), None);
+    }
+}
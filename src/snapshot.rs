@@ -0,0 +1,36 @@
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::PathBuf;
+
+pub const SNAPSHOTS_DIR: &str = "tests/snapshots";
+/// Environment variable that makes the snapshot tests write the snapshots
+/// instead of comparing against them.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "UPDATE_SNAPSHOTS";
+
+pub fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(SNAPSHOTS_DIR)
+        .join(format!("{name}.snap"))
+}
+
+/// Compares `actual` with the snapshot `tests/snapshots/<name>.snap` and
+/// shows a line diff when they differ.
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    if std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(&path, actual).unwrap();
+        return;
+    }
+    let Ok(expected) = read_to_string(&path) else {
+        panic!(
+            "No snapshot at {}, run with {UPDATE_SNAPSHOTS_VAR}=1 to create it",
+            path.display()
+        );
+    };
+    pretty_assertions::assert_eq!(
+        expected,
+        actual,
+        "snapshot {name} differs, run with {UPDATE_SNAPSHOTS_VAR}=1 to update it"
+    );
+}
@@ -1,4 +1,4 @@
-use aoc_2023_rust::{output, read_lines, Runner};
+use aoc_2023_rust::{output, Budget, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day01.txt";
 const DIGITS_IN_LETTERS: [(&str, u32); 9] = [
@@ -29,12 +29,15 @@ impl Runner for Day01 {
         (2023, 1)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 1, 2)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        if let Some(input) = input {
-            self.document = read_lines(input);
-            return;
-        }
-        self.document = read_lines(INPUT);
+        self.document = PuzzleInput::load(input, INPUT)
+            .lines()
+            .map(|line| line.to_string())
+            .collect();
     }
 
     fn part1(&mut self) -> Vec<String> {
@@ -64,6 +67,28 @@ impl Runner for Day01 {
 
         output(total)
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        const WORDS: [&str; 9] = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        let mut input = String::new();
+        for _ in 0..size.max(1) {
+            let mut line = String::new();
+            let digit_at = rng.range(0, 4);
+            for i in 0..rng.range(4, 9) {
+                match rng.below(4) {
+                    _ if i == digit_at => line.push_str(&rng.range(1, 10).to_string()),
+                    0 => line.push_str(WORDS[rng.below(WORDS.len())]),
+                    1 => line.push_str(&rng.range(1, 10).to_string()),
+                    _ => line.push((b'a' + rng.below(26) as u8) as char),
+                }
+            }
+            input.push_str(&line);
+            input.push('\n');
+        }
+        Some(input)
+    }
 }
 
 // ---------------------------------------------------
@@ -119,39 +144,15 @@ impl Indices {
 mod tests {
     const TEST_INPUT: &str = "input/day01-test.txt";
     const TEST_INPUT_P2: &str = "input/day01-test2.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::solution_tests;
 
-    use crate::solutions::day01::Day01;
-
-    #[test]
-    fn part1_test_works() {
-        let mut day01 = Day01::new();
-        day01.parse(Some(TEST_INPUT));
-        let output = day01.part1();
-        assert_eq!(output[0], "142")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day01 = Day01::new();
-        day01.parse(None);
-        let output = day01.part1();
-        assert_eq!(output[0], "54081")
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day01 = Day01::new();
-        day01.parse(Some(TEST_INPUT_P2));
-        let output = day01.part2();
-        assert_eq!(output[0], "281")
-    }
+    use crate::solutions::day01::{Day01, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day01 = Day01::new();
-        day01.parse(None);
-        let output = day01.part2();
-        assert_eq!(output[0], "54649")
+    solution_tests! {
+        Day01, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("142");
+        part1_works: real, part1 => answer("54081");
+        part2_test_works: example(TEST_INPUT_P2), part2 => answer("281");
+        part2_works: real, part2 => answer("54649");
     }
 }
@@ -1,4 +1,4 @@
-use aoc_2023_rust::{output, read_lines, Runner};
+use aoc_2023_rust::{output, Budget, PuzzleInput, Rng, Runner};
 use core::panic;
 use std::str::FromStr;
 
@@ -20,13 +20,16 @@ impl Runner for Day02 {
         (2023, 2)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 1, 1)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let input = input.unwrap_or(INPUT);
         self.games = Vec::new();
-        for line in read_lines(input) {
+        for line in PuzzleInput::load(input, INPUT).lines() {
             match Game::from_str(&line) {
                 Ok(game) => self.games.push(game),
-                Err(_) => panic!("Failed to parse game"),
+                Err(_) => panic!("{}", line.error("failed to parse game")),
             }
         }
     }
@@ -54,6 +57,26 @@ impl Runner for Day02 {
                 .sum::<u32>(),
         )
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for id in 1..=size.max(1) {
+            let sets: Vec<String> = (0..rng.range(1, 7))
+                .map(|_| {
+                    let mut colors = vec!["red", "green", "blue"];
+                    rng.shuffle(&mut colors);
+                    colors.truncate(rng.range(1, 4) as usize);
+                    colors
+                        .iter()
+                        .map(|color| format!("{} {color}", rng.range(1, 21)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            input.push_str(&format!("Game {id}: {}\n", sets.join("; ")));
+        }
+        Some(input)
+    }
 }
 
 // ---------------------------------------------------
@@ -123,39 +146,23 @@ impl FromStr for Game {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day02-test.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::{assert_snapshot, solution_tests, Runner};
 
-    use crate::solutions::day02::Day02;
+    use crate::solutions::day02::{Day02, INPUT};
 
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day02::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "8")
+    solution_tests! {
+        Day02, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("8");
+        part1_works: real, part1 => answer("2256");
+        part2_test_works: example(TEST_INPUT), part2 => answer("2286");
+        part2_works: real, part2 => answer("74229");
     }
 
     #[test]
-    fn part1_works() {
-        let mut day = Day02::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "2256")
-    }
-
-    #[test]
-    fn part2_test_works() {
+    fn games_snapshot() {
         let mut day = Day02::new();
         day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "2286")
-    }
-
-    #[test]
-    fn part2_works() {
-        let mut day = Day02::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "74229")
+        let games: Vec<String> = day.games.iter().map(|g| g.to_string()).collect();
+        assert_snapshot("day02_games", &(games.join("\n") + "\n"));
     }
 }
@@ -1,4 +1,4 @@
-use aoc_2023_rust::{output, read_lines, Point, Runner};
+use aoc_2023_rust::{output, Budget, Point, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day03.txt";
 
@@ -19,50 +19,36 @@ impl Runner for Day03 {
         (2023, 3)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 5, 5)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let input = read_lines(input.unwrap_or(INPUT));
+        let schematic = PuzzleInput::load(input, INPUT).grid(Some).unwrap();
         self.part_numbers = Vec::new();
-        self.symbols = Vec::new();
-
-        for (x, line) in input.iter().enumerate() {
-            let mut part_number = String::new();
-            for (y, c) in line.chars().enumerate() {
-                if c.is_ascii_digit() {
-                    part_number.push(c);
-                } else if c == '.' {
-                    if !part_number.is_empty() {
-                        let mut locations = Vec::new();
-                        for loc in y - part_number.len()..y {
-                            locations.push(Point::new(x as u32, loc as u32));
-                        }
-                        self.part_numbers
-                            .push(PartNumber::new(part_number.parse().unwrap(), locations));
-                        part_number.clear();
-                    }
-                } else {
-                    if !part_number.is_empty() {
-                        let mut locations = Vec::new();
-                        for loc in y - part_number.len()..y {
-                            locations.push(Point::new(x as u32, loc as u32));
-                        }
+
+        for (y, row) in schematic.rows().enumerate() {
+            let mut start = None;
+            for x in 0..=row.len() {
+                match (row.get(x).is_some_and(char::is_ascii_digit), start) {
+                    (true, None) => start = Some(x),
+                    (false, Some(first)) => {
+                        let num = row[first..x].iter().collect::<String>();
+                        let locations =
+                            (first..x).map(|x| Point::new(x as i32, y as i32)).collect();
                         self.part_numbers
-                            .push(PartNumber::new(part_number.parse().unwrap(), locations));
-                        part_number.clear();
-                    }
-                    self.symbols
-                        .push(Symbol::new(c, Point::new(x as u32, y as u32)));
-                }
-                if y == line.len() - 1 && !part_number.is_empty() {
-                    let mut locations = Vec::new();
-                    for loc in y - part_number.len()..y {
-                        locations.push(Point::new(x as u32, loc as u32));
+                            .push(PartNumber::new(num.parse().unwrap(), locations));
+                        start = None;
                     }
-                    self.part_numbers
-                        .push(PartNumber::new(part_number.parse().unwrap(), locations));
-                    part_number.clear();
+                    _ => {}
                 }
             }
         }
+        self.symbols = schematic
+            .iter()
+            .filter(|(_, c)| **c != '.' && !c.is_ascii_digit())
+            .map(|(location, c)| Symbol::new(*c, location))
+            .collect();
     }
 
     fn part1(&mut self) -> Vec<String> {
@@ -95,6 +81,32 @@ impl Runner for Day03 {
             });
         output(gear_ratio)
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        const SYMBOLS: [u8; 10] = *b"*#+$/@=%&-";
+        let size = size.max(4);
+        let mut grid = vec![vec![b'.'; size]; size];
+        for _ in 0..size * size / 8 {
+            let number = rng.range(1, 1000).to_string().into_bytes();
+            let (x, y) = (rng.below(size - number.len() + 1), rng.below(size));
+            let free = (x.saturating_sub(1)..(x + number.len() + 1).min(size))
+                .all(|x| !grid[y][x].is_ascii_digit());
+            if free {
+                grid[y][x..x + number.len()].copy_from_slice(&number);
+            }
+        }
+        for _ in 0..size * size / 20 {
+            let (x, y) = (rng.below(size), rng.below(size));
+            if grid[y][x] == b'.' {
+                grid[y][x] = *rng.choose(&SYMBOLS);
+            }
+        }
+        Some(
+            grid.into_iter()
+                .map(|row| String::from_utf8(row).unwrap() + "\n")
+                .collect(),
+        )
+    }
 }
 
 // ---------------------------------------------------
@@ -102,7 +114,7 @@ impl Runner for Day03 {
 #[derive(Debug, Default)]
 struct PartNumber {
     num: u32,
-    locations: Vec<Point<u32>>,
+    locations: Vec<Point<i32>>,
 }
 
 impl std::fmt::Display for PartNumber {
@@ -112,33 +124,25 @@ impl std::fmt::Display for PartNumber {
 }
 
 impl PartNumber {
-    fn new(num: u32, locations: Vec<Point<u32>>) -> Self {
+    fn new(num: u32, locations: Vec<Point<i32>>) -> Self {
         Self { num, locations }
     }
 
     fn is_adjacent(&self, symbol: &Symbol) -> bool {
-        for loc in &self.locations {
-            if u32::abs_diff(loc.x, symbol.location.x) <= 1
-                && u32::abs_diff(loc.y, symbol.location.y) <= 1
-            {
-                return true;
-            }
-            if loc.dist(symbol.location.x, symbol.location.y) == 1 {
-                return true;
-            }
-        }
-        false
+        self.locations
+            .iter()
+            .any(|loc| loc.chebyshev(&symbol.location) <= 1)
     }
 }
 
 #[derive(Debug, Default)]
 struct Symbol {
     symbol: char,
-    location: Point<u32>,
+    location: Point<i32>,
 }
 
 impl Symbol {
-    fn new(symbol: char, location: Point<u32>) -> Self {
+    fn new(symbol: char, location: Point<i32>) -> Self {
         Self { symbol, location }
     }
 }
@@ -157,39 +161,15 @@ impl std::fmt::Display for Symbol {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day03-test.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::solution_tests;
 
-    use crate::solutions::day03::Day03;
-
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day03::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "4361")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day = Day03::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "550064")
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day = Day03::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "467835")
-    }
+    use crate::solutions::day03::{Day03, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day = Day03::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "85010461")
+    solution_tests! {
+        Day03, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("4361");
+        part1_works: real, part1 => answer("550064");
+        part2_test_works: example(TEST_INPUT), part2 => answer("467835");
+        part2_works: real, part2 => answer("85010461");
     }
 }
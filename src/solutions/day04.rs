@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use aoc_2023_rust::{output, read_lines, Runner};
+use aoc_2023_rust::{output, Alternative, Budget, Memo, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day04.txt";
 
@@ -22,24 +22,20 @@ impl Runner for Day04 {
         (2023, 4)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(2, 1, 1)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let input = read_lines(input.unwrap_or(INPUT));
+        let input = PuzzleInput::load(input, INPUT);
         self.cards = HashMap::new();
 
-        for line in input {
-            let (card, rest) = line.split_once(':').unwrap();
-            let id: u32 = card.split_once(' ').unwrap().1.trim().parse().unwrap();
-            let (winning_numbers, own_numbers) = rest.trim().split_once('|').unwrap();
-            let winning_numbers: Vec<u32> = winning_numbers
-                .split(' ')
-                .filter(|n| !n.trim().is_empty())
-                .map(|n| n.trim().parse().unwrap())
-                .collect();
-            let own_numbers: Vec<u32> = own_numbers
-                .split(' ')
-                .filter(|n| !n.trim().is_empty())
-                .map(|n| n.trim().parse().unwrap())
-                .collect();
+        for line in input.lines() {
+            let (card, numbers) = line.key_value(':').unwrap();
+            let id: u32 = card.key_value(' ').unwrap().1.parse().unwrap();
+            let (winning_numbers, own_numbers) = numbers.key_value('|').unwrap();
+            let winning_numbers: Vec<u32> = winning_numbers.ints().unwrap();
+            let own_numbers: Vec<u32> = own_numbers.ints().unwrap();
             self.cards
                 .insert(id, Card::new(id, winning_numbers, own_numbers));
         }
@@ -55,6 +51,7 @@ impl Runner for Day04 {
     }
 
     fn part2(&mut self) -> Vec<String> {
+        let mut copies = Memo::new();
         let mut total = 0;
         for card in self.cards.values() {
             total += 1;
@@ -65,31 +62,98 @@ impl Runner for Day04 {
             total += matching_numbers;
             let next_cards = card.id + 1..=card.id + matching_numbers;
             for card_id in next_cards {
-                total += find_all_copies(&self.cards, card_id);
+                total += find_all_copies(&self.cards, &mut copies, card_id);
             }
         }
 
         output(total)
     }
+
+    fn alternatives(&self) -> Vec<Alternative> {
+        vec![Alternative::new(2, "counts")]
+    }
+
+    fn run_alternative(&mut self, alternative: &Alternative) -> Vec<String> {
+        match (alternative.part, alternative.name) {
+            (2, "counts") => output(count_copies(&self.cards)),
+            _ => panic!(
+                "No implementation {} of part {}",
+                alternative.name, alternative.part
+            ),
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let cards = size.max(1);
+        let mut input = String::new();
+        for id in 1..=cards {
+            // Mostly few matches, so that the number of copies stays bounded.
+            let matches = match rng.below(20) {
+                0..=9 => 0,
+                10..=15 => 1,
+                16..=18 => 2,
+                _ => rng.range(3, 6) as usize,
+            }
+            .min(cards - id);
+            let numbers = rng.distinct(10 + 25 - matches, 1, 100);
+            let winning = &numbers[..10];
+            let mut own: Vec<u64> = numbers[10..].to_vec();
+            own.extend(&winning[..matches]);
+            rng.shuffle(&mut own);
+            let format = |numbers: &[u64]| {
+                numbers
+                    .iter()
+                    .map(|n| format!("{n:2}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            input.push_str(&format!(
+                "Card {id:3}: {} | {}\n",
+                format(winning),
+                format(&own)
+            ));
+        }
+        Some(input)
+    }
 }
 
 // ---------------------------------------------------
 
-fn find_all_copies(cards: &HashMap<CardId, Card>, card_id: u32) -> u32 {
-    let mut total = 0;
-    if let Some(card) = cards.get(&card_id) {
-        let matching_numbers = card.matching_numbers;
-        if matching_numbers == 0 {
+/// Copies won by the card `card_id` and the copies they win in turn, which
+/// only depend on the card, so that each is counted once.
+fn find_all_copies(
+    cards: &HashMap<CardId, Card>,
+    copies: &mut Memo<CardId, u32>,
+    card_id: u32,
+) -> u32 {
+    copies.get_or_compute(card_id, |copies| {
+        let Some(card) = cards.get(&card_id) else {
             return 0;
+        };
+        let next_cards = card.id + 1..=card.id + card.matching_numbers;
+        let won: u32 = next_cards
+            .map(|next_card| find_all_copies(cards, copies, next_card))
+            .sum();
+        card.matching_numbers + won
+    })
+}
+
+/// Counts the copies in one pass over the cards in order: each copy of a card
+/// wins one copy of each of the next `matching_numbers` cards.
+fn count_copies(cards: &HashMap<CardId, Card>) -> u32 {
+    let mut ids: Vec<CardId> = cards.keys().copied().collect();
+    ids.sort();
+    let mut copies: HashMap<CardId, u32> = ids.iter().map(|id| (*id, 1)).collect();
+
+    for id in ids {
+        let count = copies[&id];
+        for next in id + 1..=id + cards[&id].matching_numbers {
+            if let Some(c) = copies.get_mut(&next) {
+                *c += count;
+            }
         }
-        total += matching_numbers;
-        let next_cards = card.id + 1..=card.id + matching_numbers;
-        for next_card in next_cards {
-            total += find_all_copies(cards, next_card);
-        }
-        return total;
     }
-    0
+    copies.values().sum()
 }
 
 #[derive(Debug, Default)]
@@ -146,39 +210,25 @@ impl Card {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day04-test.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::{assert_snapshot, solution_tests, Runner};
 
-    use crate::solutions::day04::Day04;
+    use crate::solutions::day04::{Day04, INPUT};
 
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day04::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "13")
+    solution_tests! {
+        Day04, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("13");
+        part1_works: real, part1 => answer("17782");
+        part2_test_works: example(TEST_INPUT), part2 => answer("30");
+        part2_works: real, part2 => answer("8477787");
     }
 
     #[test]
-    fn part1_works() {
-        let mut day = Day04::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "17782")
-    }
-
-    #[test]
-    fn part2_test_works() {
+    fn cards_snapshot() {
         let mut day = Day04::new();
         day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "30")
-    }
-
-    #[test]
-    fn part2_works() {
-        let mut day = Day04::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "8477787")
+        let mut cards: Vec<_> = day.cards.values().collect();
+        cards.sort_by_key(|c| c.id);
+        let cards: String = cards.iter().map(|c| c.to_string()).collect();
+        assert_snapshot("day04_cards", &cards);
     }
 }
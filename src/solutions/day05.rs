@@ -1,6 +1,8 @@
 use std::str::FromStr;
 
-use aoc_2023_rust::{output, read_lines, Runner};
+use aoc_2023_rust::{
+    output, Alternative, Assumption, Budget, Interval, IntervalSet, PuzzleInput, Rng, Runner,
+};
 
 const INPUT: &str = "input/day05.txt";
 
@@ -21,43 +23,24 @@ impl Runner for Day05 {
         (2023, 5)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 1, 1)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let mut input = read_lines(input.unwrap_or(INPUT));
-        while !input.is_empty() {
-            let line = input.remove(0);
-            if line.starts_with("seeds") {
-                let (_, numbers) = line.split_once(':').unwrap();
-                self.seeds = numbers
-                    .trim()
-                    .split(' ')
-                    .map(|n| n.parse().unwrap())
-                    .collect();
-                continue;
-            }
-            if line.contains("map") {
-                let (dest_type, source_type) = line.split_once("-to-").unwrap();
-                let dest_type = dest_type.trim();
-                let (source_type, _) = source_type.trim().split_once(' ').unwrap();
-                let mut ranges = Vec::new();
-                loop {
-                    if !input.is_empty() && input[0].contains("map") {
-                        break;
-                    }
-                    if input.is_empty() {
-                        break;
-                    }
-                    let line = input.remove(0);
-                    if line.trim().is_empty() {
-                        break;
-                    }
-                    ranges.push(line.parse().unwrap());
-                }
-                self.maps.push(Map::new(
-                    dest_type.to_string(),
-                    source_type.to_string(),
-                    ranges,
-                ));
-            }
+        let sections = PuzzleInput::load(input, INPUT).sections();
+        let (seeds, maps) = sections.split_first().expect("missing seeds");
+        let (seeds, _) = seeds.split_header().unwrap();
+        self.seeds = seeds.key_value(':').unwrap().1.ints().unwrap();
+        for map in maps {
+            let (header, ranges) = map.split_header().unwrap();
+            let (dest_type, source_type) = header.split_once("-to-").unwrap();
+            let (source_type, _) = source_type.split_once(' ').unwrap();
+            self.maps.push(Map::new(
+                dest_type.to_string(),
+                source_type.to_string(),
+                ranges.lines().map(|line| line.parse().unwrap()).collect(),
+            ));
         }
     }
 
@@ -84,25 +67,136 @@ impl Runner for Day05 {
     }
 
     fn part2(&mut self) -> Vec<String> {
-        let mut min_location = usize::MAX;
-        for index in (0..self.seeds.len()).step_by(2) {
-            let seed_start = self.seeds[index];
-            let seed_length = self.seeds[index + 1];
-            for seed in seed_start..seed_start + seed_length {
-                let mut original_seed = seed;
-                for map in &self.maps {
-                    for range in &map.source_maps {
-                        let next_seed = range.map_source_to_destination(original_seed);
-                        if next_seed != original_seed {
-                            original_seed = next_seed;
-                            break;
+        let mut seeds: IntervalSet<usize> = self
+            .seeds
+            .chunks(2)
+            .map(|pair| Interval::with_length(pair[0], pair[1]))
+            .collect();
+        for map in &self.maps {
+            // Sources in a range move to its destination, the rest stay.
+            let mut mapped = IntervalSet::new();
+            for range in &map.source_maps {
+                let source = IntervalSet::from(range.source_interval());
+                for moved in seeds.intersection(&source).intervals() {
+                    mapped.insert(moved.shift(range.source, range.destination));
+                }
+                seeds = seeds.difference(&source);
+            }
+            seeds = seeds.union(&mapped);
+        }
+        output(seeds.min().unwrap())
+    }
+
+    fn assumptions(&self) -> Vec<Assumption> {
+        vec![Assumption::check(
+            "the ranges within one map don't overlap",
+            self.maps.iter().find_map(Map::overlap),
+        )]
+    }
+
+    fn alternatives(&self) -> Vec<Alternative> {
+        vec![Alternative::new(2, "ranges")]
+    }
+
+    fn run_alternative(&mut self, alternative: &Alternative) -> Vec<String> {
+        match (alternative.part, alternative.name) {
+            (2, "ranges") => output(self.lowest_location_of_ranges()),
+            _ => panic!(
+                "No implementation {} of part {}",
+                alternative.name, alternative.part
+            ),
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        const CATEGORIES: [&str; 8] = [
+            "seed",
+            "soil",
+            "fertilizer",
+            "water",
+            "light",
+            "temperature",
+            "humidity",
+            "location",
+        ];
+        let span = 1000 * size.max(1) as u64;
+        let seeds: Vec<String> = (0..1 + size / 2)
+            .map(|_| {
+                let start = rng.range(0, span);
+                format!(
+                    "{start} {}",
+                    rng.range(1, (span - start).min(span / 10) + 1)
+                )
+            })
+            .collect();
+
+        let mut input = format!("seeds: {}\n", seeds.join(" "));
+        for kind in CATEGORIES.windows(2) {
+            input.push_str(&format!("\n{}-to-{} map:\n", kind[0], kind[1]));
+            // Cut 0..span into pieces and move each piece to its place in a
+            // random order, so that the ranges of a map never overlap.
+            let cut_count = rng.range(1, 6) as usize;
+            let mut cuts = rng.distinct(cut_count, 1, span);
+            cuts.extend([0, span]);
+            cuts.sort();
+            let pieces: Vec<(u64, u64)> = cuts.windows(2).map(|c| (c[0], c[1] - c[0])).collect();
+            let mut order: Vec<usize> = (0..pieces.len()).collect();
+            rng.shuffle(&mut order);
+            let mut destinations = vec![0; pieces.len()];
+            let mut destination = 0;
+            for i in order {
+                destinations[i] = destination;
+                destination += pieces[i].1;
+            }
+            for ((source, length), destination) in pieces.iter().zip(destinations) {
+                input.push_str(&format!("{destination} {source} {length}\n"));
+            }
+        }
+        Some(input)
+    }
+}
+
+impl Day05 {
+    /// Maps whole seed ranges instead of single seeds, splitting a range
+    /// wherever it straddles the edge of a map range.
+    fn lowest_location_of_ranges(&self) -> usize {
+        let mut ranges: Vec<(usize, usize)> = self
+            .seeds
+            .chunks(2)
+            .map(|pair| (pair[0], pair[0] + pair[1]))
+            .collect();
+
+        for map in &self.maps {
+            let mut mapped = Vec::new();
+            while let Some((start, end)) = ranges.pop() {
+                let overlap = map.source_maps.iter().find_map(|r| {
+                    let (from, to) = (start.max(r.source), end.min(r.source + r.length));
+                    (from < to).then_some((r, from, to))
+                });
+                match overlap {
+                    Some((range, from, to)) => {
+                        mapped.push((
+                            range.map_source_to_destination(from),
+                            range.map_source_to_destination(to - 1) + 1,
+                        ));
+                        if start < from {
+                            ranges.push((start, from));
+                        }
+                        if to < end {
+                            ranges.push((to, end));
                         }
                     }
+                    None => mapped.push((start, end)),
                 }
-                min_location = min_location.min(original_seed);
             }
+            ranges = mapped;
         }
-        output(min_location)
+        ranges
+            .iter()
+            .filter(|(start, end)| start < end)
+            .map(|(start, _)| *start)
+            .min()
+            .unwrap()
     }
 }
 
@@ -123,6 +217,24 @@ impl Map {
             source_maps,
         }
     }
+
+    /// Two ranges of the map that both map some source.
+    fn overlap(&self) -> Option<String> {
+        let mut ranges: Vec<&Range> = self.source_maps.iter().collect();
+        ranges.sort_by_key(|range| range.source);
+        ranges.windows(2).find_map(|pair| {
+            (pair[0].source + pair[0].length > pair[1].source).then(|| {
+                format!(
+                    "{}-to-{} ranges `{}` and `{}` both map source {}",
+                    self.dest_type,
+                    self.source_type,
+                    pair[0].line(),
+                    pair[1].line(),
+                    pair[1].source
+                )
+            })
+        })
+    }
 }
 
 impl std::fmt::Display for Map {
@@ -151,6 +263,15 @@ impl Range {
         }
     }
 
+    fn source_interval(&self) -> Interval<usize> {
+        Interval::with_length(self.source, self.length)
+    }
+
+    /// The range as written in the input.
+    fn line(&self) -> String {
+        format!("{} {} {}", self.destination, self.source, self.length)
+    }
+
     fn map_source_to_destination(&self, source: usize) -> usize {
         if source < self.source || source >= self.source + self.length {
             return source;
@@ -167,14 +288,17 @@ impl std::fmt::Display for Range {
 }
 
 impl FromStr for Range {
-    type Err = ();
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_whitespace();
-        let destination = parts.next().unwrap().parse().unwrap();
-        let source = parts.next().unwrap().parse().unwrap();
-        let length = parts.next().unwrap().parse().unwrap();
-        Ok(Self::new(source, destination, length))
+        let numbers = s
+            .split_whitespace()
+            .map(|n| n.parse().map_err(|e| format!("{e}")))
+            .collect::<Result<Vec<usize>, String>>()?;
+        match numbers[..] {
+            [destination, source, length] => Ok(Self::new(source, destination, length)),
+            _ => Err(format!("expected 3 numbers, found {}", numbers.len())),
+        }
     }
 }
 
@@ -182,39 +306,15 @@ impl FromStr for Range {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day05-test.txt";
-    use aoc_2023_rust::Runner;
-
-    use crate::solutions::day05::Day05;
+    use aoc_2023_rust::solution_tests;
 
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day05::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "35")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day = Day05::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "662197086")
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day = Day05::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "46")
-    }
+    use crate::solutions::day05::{Day05, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day = Day05::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "8477787")
+    solution_tests! {
+        Day05, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("35");
+        part1_works: real, part1 => answer("662197086");
+        part2_test_works: example(TEST_INPUT), part2 => answer("46");
+        part2_works: real, part2 => answer("52510809");
     }
 }
@@ -1,6 +1,6 @@
 use std::iter::zip;
 
-use aoc_2023_rust::{output, read_lines, Runner};
+use aoc_2023_rust::{output, quadratic_below_zero, Budget, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day06.txt";
 
@@ -20,22 +20,20 @@ impl Runner for Day06 {
         (2023, 6)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 1, 1)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let input = read_lines(input.unwrap_or(INPUT));
+        let input = PuzzleInput::load(input, INPUT);
         let mut times: Vec<u64> = Vec::new();
         let mut distances: Vec<u64> = Vec::new();
-        for line in input {
-            if line.starts_with("Time:") {
-                let (_, timings) = line.split_once(':').unwrap();
-                timings.split_whitespace().for_each(|t| {
-                    times.push(t.trim().parse().unwrap());
-                });
-            }
-            if line.starts_with("Distance:") {
-                let (_, dist) = line.split_once(':').unwrap();
-                dist.split_whitespace().for_each(|t| {
-                    distances.push(t.trim().parse().unwrap());
-                });
+        for line in input.lines() {
+            let (name, numbers) = line.key_value(':').unwrap();
+            match name.text {
+                "Time" => times = numbers.ints().unwrap(),
+                "Distance" => distances = numbers.ints().unwrap(),
+                _ => panic!("{}", name.error(format!("unknown record `{name}`"))),
             }
         }
         for (time, distance) in zip(times, distances) {
@@ -74,6 +72,21 @@ impl Runner for Day06 {
 
         output(race.beat_record())
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let races: Vec<(u64, u64)> = (0..size.clamp(1, 3))
+            .map(|_| {
+                let time = rng.range(7, 100);
+                (time, rng.range(0, (time / 2) * (time - time / 2)))
+            })
+            .collect();
+        let row = |values: Vec<u64>| values.iter().map(|v| format!("{v:>4}")).collect::<String>();
+        Some(format!(
+            "Time:    {}\nDistance:{}\n",
+            row(races.iter().map(|r| r.0).collect()),
+            row(races.iter().map(|r| r.1).collect())
+        ))
+    }
 }
 
 // ---------------------------------------------------
@@ -89,16 +102,12 @@ impl Race {
         Self { time, distance }
     }
 
+    /// Holding the button for `speed` ms beats the record when
+    /// `speed * (time - speed) > distance`, so `speed² - time * speed +
+    /// distance < 0`.
     fn beat_record(&self) -> u64 {
-        let mut beats = 0;
-        for speed in 0..=self.time {
-            let time = self.time - speed;
-            let distance = time * speed;
-            if distance > self.distance {
-                beats += 1;
-            }
-        }
-        beats
+        quadratic_below_zero(1, -(self.time as i64), self.distance as i64)
+            .map_or(0, |speeds| (speeds.end() - speeds.start() + 1) as u64)
     }
 }
 
@@ -106,39 +115,15 @@ impl Race {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day06-test.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::solution_tests;
 
-    use crate::solutions::day06::Day06;
-
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day06::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "288")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day = Day06::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "449550")
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day = Day06::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "71503")
-    }
+    use crate::solutions::day06::{Day06, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day = Day06::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "28360140")
+    solution_tests! {
+        Day06, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("288");
+        part1_works: real, part1 => answer("449550");
+        part2_test_works: example(TEST_INPUT), part2 => answer("71503");
+        part2_works: real, part2 => answer("28360140");
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use aoc_2023_rust::{output, read_lines, Runner};
+use aoc_2023_rust::{output, Budget, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day07.txt";
 
@@ -20,11 +20,13 @@ impl Runner for Day07 {
         (2023, 7)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 70, 70)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let input = read_lines(input.unwrap_or(INPUT));
-        for line in input {
-            let line = line.trim();
-            self.hands.push(Hand::from(line));
+        for line in PuzzleInput::load(input, INPUT).lines() {
+            self.hands.push(Hand::from(line.trim()));
         }
     }
 
@@ -49,6 +51,20 @@ impl Runner for Day07 {
                 .sum::<u32>(),
         )
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        const CARDS: [char; 13] = [
+            '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+        ];
+        let mut input = String::new();
+        for _ in 0..size.max(1) {
+            // Draw from a few card kinds to get pairs and full houses too.
+            let kinds: Vec<char> = (0..rng.range(1, 6)).map(|_| *rng.choose(&CARDS)).collect();
+            let hand: String = (0..5).map(|_| *rng.choose(&kinds)).collect();
+            input.push_str(&format!("{hand} {}\n", rng.range(1, 1001)));
+        }
+        Some(input)
+    }
 }
 
 // ---------------------------------------------------
@@ -378,39 +394,15 @@ fn sort_hands(hands: &mut [Hand]) {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day07-test.txt";
-    use aoc_2023_rust::Runner;
-
-    use crate::solutions::day07::Day07;
+    use aoc_2023_rust::solution_tests;
 
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day07::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "6440")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day = Day07::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "246163188")
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day = Day07::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "5905")
-    }
+    use crate::solutions::day07::{Day07, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day = Day07::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "245794069")
+    solution_tests! {
+        Day07, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("6440");
+        part1_works: real, part1 => answer("246163188");
+        part2_test_works: example(TEST_INPUT), part2 => answer("5905");
+        part2_works: real, part2 => answer("245794069");
     }
 }
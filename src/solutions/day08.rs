@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use aoc_2023_rust::{output, read_lines, Runner};
+use aoc_2023_rust::{bfs, lcm_all, output, Assumption, Budget, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day08.txt";
 
@@ -20,32 +20,33 @@ impl Runner for Day08 {
         (2023, 8)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 10, 60)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let input = read_lines(input.unwrap_or(INPUT));
-        self.document.instructions = input
-            .first()
-            .unwrap()
+        let input = PuzzleInput::load(input, INPUT);
+        let (instructions, nodes) = input.split_header().unwrap();
+        self.document.instructions = instructions
             .chars()
             .map(|c| match c {
                 'L' => Instruction::Left,
                 'R' => Instruction::Right,
-                _ => panic!("Invalid instruction"),
+                _ => panic!(
+                    "{}",
+                    instructions.error(format!("invalid instruction `{c}`"))
+                ),
             })
             .collect();
-        for line in input {
-            if line.contains('(') {
-                let (node, nodes) = line.split_once(" = ").unwrap();
-
-                let nodes = nodes
-                    .chars()
-                    .skip(1)
-                    .take(nodes.len() - 2)
-                    .collect::<String>();
-                let (node_a, node_b) = nodes.split_once(", ").unwrap();
-                self.document
-                    .nodes
-                    .insert(Node::new(node), (Node::new(node_a), Node::new(node_b)));
-            }
+        for line in nodes.lines() {
+            let (node, next) = line.key_value('=').unwrap();
+            let (node_a, node_b) = next
+                .trim_matches(|c| c == '(' || c == ')')
+                .split_once(", ")
+                .unwrap();
+            self.document
+                .nodes
+                .insert(Node::new(&node), (Node::new(node_a), Node::new(node_b)));
         }
     }
 
@@ -56,6 +57,62 @@ impl Runner for Day08 {
     fn part2(&mut self) -> Vec<String> {
         output(self.document.find_simultaneously_all_nodes_ending_with_z())
     }
+
+    fn assumptions(&self) -> Vec<Assumption> {
+        vec![Assumption::check(
+            "each ..A ghost reaches a ..Z node on a clean cycle",
+            self.document.ghost_off_cycle(),
+        )]
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let instructions: String = (0..rng.range(2, 10))
+            .map(|_| if rng.chance(0.5) { 'L' } else { 'R' })
+            .collect();
+        let mut names = (0..).map(|i: usize| {
+            let letter = |n: usize| (b'B' + (n % 24) as u8) as char;
+            format!("{}{}{}", letter(i / 576), letter(i / 24), letter(i))
+        });
+
+        // Each ghost walks a cycle of two lanes from its start ..A to its end
+        // ..Z, and from the end back to the first step of the cycle, so that
+        // it is at the end every `length` steps.
+        let mut nodes = Vec::new();
+        for ghost in 0..size.clamp(1, 6) {
+            let (start, end) = match ghost {
+                0 => ("AAA".to_string(), "ZZZ".to_string()),
+                _ => {
+                    let prefix = (b'A' + ghost as u8) as char;
+                    (format!("{prefix}{prefix}A"), format!("{prefix}{prefix}Z"))
+                }
+            };
+            let length = rng.range(2, 12) as usize;
+            let lanes: Vec<[String; 2]> = (1..length)
+                .map(|_| [names.next().unwrap(), names.next().unwrap()])
+                .collect();
+            let mut step = |from: String, to: &[String; 2]| {
+                let (left, right) = if rng.chance(0.5) {
+                    (&to[0], &to[1])
+                } else {
+                    (&to[1], &to[0])
+                };
+                nodes.push(format!("{from} = ({left}, {right})"));
+            };
+            step(start, &lanes[0]);
+            step(end.clone(), &lanes[0]);
+            for i in 0..lanes.len() {
+                let next = lanes
+                    .get(i + 1)
+                    .cloned()
+                    .unwrap_or_else(|| [end.clone(), end.clone()]);
+                for node in lanes[i].clone() {
+                    step(node, &next);
+                }
+            }
+        }
+        rng.shuffle(&mut nodes);
+        Some(format!("{instructions}\n\n{}\n", nodes.join("\n")))
+    }
 }
 
 // ---------------------------------------------------
@@ -73,7 +130,7 @@ struct Node {
 
 impl std::fmt::Display for Node {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", &self.name[..self.name.len() - 1])
+        write!(f, "{}", self.name)
     }
 }
 
@@ -104,21 +161,17 @@ impl std::fmt::Display for Document {
             }
         }
         writeln!(f)?;
-        for (name, node) in &self.nodes {
-            write!(f, "{} {} {} ", name, node.0, node.1)?;
+        let mut names: Vec<&Node> = self.nodes.keys().collect();
+        names.sort_by_key(|node| &node.name);
+        for name in names {
+            let (left, right) = &self.nodes[name];
+            writeln!(f, "{name} = ({left}, {right})")?;
         }
         Ok(())
     }
 }
 
 impl Document {
-    fn get_all_node_names(&self) -> Vec<String> {
-        self.nodes
-            .keys()
-            .map(|node| node.name.clone())
-            .collect::<Vec<String>>()
-    }
-
     fn get_all_nodes_ending_with_a(&self) -> Vec<&Node> {
         self.nodes
             .keys()
@@ -126,65 +179,92 @@ impl Document {
             .collect()
     }
 
-    fn find_simultaneously_all_nodes_ending_with_z(&self) -> u32 {
-        let current_nodes = self.get_all_nodes_ending_with_a();
-        let all_nodes: Vec<String> = self.get_all_node_names();
-        let mut node_positions: Vec<[u16; 2]> = Vec::new();
-        for nodes in self.nodes.values() {
-            let node_left = all_nodes.iter().position(|n| n == &nodes.0.name).unwrap();
-            let node_right = all_nodes.iter().position(|n| n == &nodes.1.name).unwrap();
-            node_positions.push([node_left as u16, node_right as u16]);
-        }
-        let mut current_node_positions: Vec<u16> = Vec::new();
-        for node in current_nodes.iter() {
-            let node_position = all_nodes.iter().position(|n| n == &node.name).unwrap();
-            current_node_positions.push(node_position as u16);
-        }
-
-        let instructions: Vec<usize> = self
-            .instructions
-            .iter()
-            .map(|instruction| match instruction {
-                Instruction::Left => 0,
-                Instruction::Right => 1,
-            })
-            .collect();
-
-        let mut steps = 0;
-        for instruction in instructions.iter().cycle() {
-            current_node_positions = current_node_positions
-                .iter()
-                .map(|node| node_positions[*node as usize][*instruction])
-                .collect();
-            steps += 1;
-            if current_node_positions
-                .iter()
-                .all(|node| all_nodes[*node as usize].ends_with('Z'))
-            {
-                return steps;
-            }
-        }
-        steps
+    /// Steps until all ghosts are at a ..Z node at once. As each ghost is at
+    /// one exactly every `n` steps, see `ghost_off_cycle`, that is the lcm of
+    /// the steps to their first ..Z nodes.
+    fn find_simultaneously_all_nodes_ending_with_z(&self) -> u64 {
+        let steps = self.get_all_nodes_ending_with_a().into_iter().map(|start| {
+            self.steps(start, |node| node.name.ends_with('Z'))
+                .expect("a ghost never reaches a ..Z node") as u64
+        });
+        lcm_all(steps).expect("the steps overflow")
     }
 
-    fn find_zzz_node(&self) -> u32 {
-        let mut current_node = Node::new("AAA");
-        let mut steps = 0;
-        for instruction in self.instructions.iter().cycle() {
-            match instruction {
-                Instruction::Left => {
-                    current_node = self.nodes.get(&current_node).unwrap().0.clone();
+    /// The first ghost that is not at a ..Z node exactly every `n` steps,
+    /// where `n` is the number of steps to its first ..Z node.
+    fn ghost_off_cycle(&self) -> Option<String> {
+        if self.instructions.is_empty() {
+            return None;
+        }
+        let mut starts = self.get_all_nodes_ending_with_a();
+        starts.sort_by_key(|node| &node.name);
+        for start in starts {
+            // Walks until the ghost is at a node on the same instruction
+            // again, noting the steps where it is at a ..Z node.
+            let mut seen: HashMap<(&Node, usize), usize> = HashMap::new();
+            let mut ends = Vec::new();
+            let (mut node, mut steps) = (start, 0);
+            let (cycle_start, period) = loop {
+                let instruction = steps % self.instructions.len();
+                if let Some(first) = seen.insert((node, instruction), steps) {
+                    break (first, steps - first);
                 }
-                Instruction::Right => {
-                    current_node = self.nodes.get(&current_node).unwrap().1.clone();
+                if node.name.ends_with('Z') {
+                    ends.push(steps);
                 }
+                let Some((left, right)) = self.nodes.get(node) else {
+                    return Some(format!("ghost {start} reaches {node}, which leads nowhere"));
+                };
+                node = match self.instructions[instruction] {
+                    Instruction::Left => left,
+                    Instruction::Right => right,
+                };
+                steps += 1;
+            };
+            let Some(&first) = ends.first() else {
+                return Some(format!("ghost {start} never reaches a ..Z node"));
+            };
+            if let Some((expected, end)) = (first..)
+                .step_by(first)
+                .zip(&ends)
+                .find(|(expected, end)| expected != *end)
+            {
+                return Some(format!(
+                    "ghost {start} reaches a ..Z node after {first} steps, \
+                     then after {end} steps instead of {expected}"
+                ));
             }
-            steps += 1;
-            if current_node.is_leaf() {
-                return steps;
+            if period % first != 0 || ends.len() != (cycle_start + period - 1) / first {
+                return Some(format!(
+                    "ghost {start} reaches a ..Z node after {first} steps, \
+                     but its walk repeats every {period} steps from step {cycle_start}"
+                ));
             }
         }
-        steps
+        None
+    }
+
+    fn find_zzz_node(&self) -> usize {
+        self.steps(&Node::new("AAA"), Node::is_leaf)
+            .expect("ZZZ is never reached")
+    }
+
+    /// Steps from `start` to a node that is an `end`, searching the states of
+    /// a node and the index of the instruction to follow from it.
+    fn steps(&self, start: &Node, end: impl Fn(&Node) -> bool) -> Option<usize> {
+        let search = bfs(
+            [(start, 0)],
+            |(node, instruction)| {
+                let (left, right) = &self.nodes[*node];
+                let next = match self.instructions[*instruction] {
+                    Instruction::Left => left,
+                    Instruction::Right => right,
+                };
+                [(next, (instruction + 1) % self.instructions.len())]
+            },
+            |(node, _)| end(node),
+        );
+        search.goal_distance()
     }
 }
 
@@ -194,47 +274,38 @@ mod tests {
     const TEST_INPUT: &str = "input/day08-test.txt";
     const TEST_INPUT_2: &str = "input/day08-test2.txt";
     const TEST_INPUT_3: &str = "input/day08-test3.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::{assert_snapshot, solution_tests, Runner};
 
-    use crate::solutions::day08::Day08;
+    use crate::solutions::day08::{Day08, INPUT};
 
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day08::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "2")
+    solution_tests! {
+        Day08, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("2");
+        part1_test2_works: example(TEST_INPUT_2), part1 => answer("6");
+        part1_works: real, part1 => answer("17621");
+        part2_test_works: example(TEST_INPUT_3), part2 => answer("6");
+        part2_works: real, part2 => answer("20685524831999");
     }
 
     #[test]
-    fn part1_test2_works() {
+    fn document_snapshot() {
         let mut day = Day08::new();
-        day.parse(Some(TEST_INPUT_2));
-        let output = day.part1();
-        assert_eq!(output[0], "6")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day = Day08::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "17621")
+        day.parse(Some(TEST_INPUT));
+        assert_snapshot("day08_document", &day.document.to_string());
     }
 
     #[test]
-    fn part2_test_works() {
+    fn ghost_cycles() {
         let mut day = Day08::new();
         day.parse(Some(TEST_INPUT_3));
-        let output = day.part2();
-        assert_eq!(output[0], "6")
-    }
+        assert!(day.assumptions()[0].holds());
 
-    #[test]
-    fn part2_works() {
+        // ZZZ leads back to itself, so AAA is at ZZZ on every step after two.
         let mut day = Day08::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "245794069")
+        day.parse(Some(TEST_INPUT));
+        assert_eq!(
+            day.assumptions()[0].counter_example.as_deref(),
+            Some("ghost AAA reaches a ..Z node after 2 steps, then after 3 steps instead of 4")
+        );
     }
 }
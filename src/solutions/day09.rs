@@ -1,4 +1,4 @@
-use aoc_2023_rust::{output, read_lines, Runner};
+use aoc_2023_rust::{output, Budget, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day09.txt";
 
@@ -18,16 +18,13 @@ impl Runner for Day09 {
         (2023, 9)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 1, 1)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let input = read_lines(input.unwrap_or(INPUT));
-        for line in input {
-            let line = line.split(' ').collect::<Vec<&str>>();
-            let history: History = History::new(
-                line.iter()
-                    .map(|x| x.parse::<i32>().unwrap())
-                    .collect::<Vec<i32>>(),
-            );
-            self.dataset.push(history);
+        for line in PuzzleInput::load(input, INPUT).lines() {
+            self.dataset.push(History::new(line.ints().unwrap()));
         }
     }
 
@@ -48,6 +45,27 @@ impl Runner for Day09 {
                 .sum::<i32>(),
         )
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size.max(1) {
+            let coefficients: Vec<i64> = (0..rng.range(1, 5))
+                .map(|_| rng.range(0, 21) as i64 - 10)
+                .collect();
+            let values: Vec<String> = (0..21i64)
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |value, c| value * x + c)
+                        .to_string()
+                })
+                .collect();
+            input.push_str(&values.join(" "));
+            input.push('\n');
+        }
+        Some(input)
+    }
 }
 
 // ---------------------------------------------------
@@ -110,39 +128,15 @@ impl History {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day09-test.txt";
-    use aoc_2023_rust::Runner;
-
-    use crate::solutions::day09::Day09;
+    use aoc_2023_rust::solution_tests;
 
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day09::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "114")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day = Day09::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "1992273652")
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day = Day09::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "2")
-    }
+    use crate::solutions::day09::{Day09, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day = Day09::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "1012")
+    solution_tests! {
+        Day09, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("114");
+        part1_works: real, part1 => answer("1992273652");
+        part2_test_works: example(TEST_INPUT), part2 => answer("2");
+        part2_works: real, part2 => answer("1012");
     }
 }
@@ -1,10 +1,11 @@
-use std::collections::{HashMap, HashSet};
-
-use aoc_2023_rust::{output, read_lines, OutputStatus, Point, Runner};
+use aoc_2023_rust::{
+    bfs, output, Assumption, Budget, Direction, OutputStatus, Point, PuzzleInput, Rng, Runner,
+    SparseGrid,
+};
 
 const INPUT: &str = "input/day10.txt";
 
-type Grid = HashMap<Point<i32>, Tile>;
+type Grid = SparseGrid<Tile>;
 
 #[derive(Debug, Default)]
 pub struct Day10 {
@@ -22,285 +23,238 @@ impl Runner for Day10 {
         (2023, 10)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(3, 25, 1)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let input = read_lines(input.unwrap_or(INPUT));
-        for (y, line) in input.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                if c == '.' {
-                    continue;
-                }
-                self.map
-                    .insert(Point::new(x as i32, y as i32), Tile::Pipe(c));
-            }
-        }
+        let input = PuzzleInput::load(input, INPUT);
+        self.map = SparseGrid::parse(input.text(), Tile::Ground, |c| match c {
+            '.' => Tile::Ground,
+            c => Tile::Pipe(c),
+        });
     }
 
     fn part1(&mut self) -> Vec<String> {
-        let starting_position = *self
-            .map
-            .iter()
-            .find(|(_, t)| **t == Tile::Pipe('S'))
-            .unwrap()
-            .0;
-        let mut current_position = find_next_location_from_start(&self.map, starting_position);
-        let mut main_loop: Vec<Point<i32>> = Vec::new();
-        let mut visited: HashSet<Point<i32>> = HashSet::new();
-
-        main_loop.push(starting_position);
-        main_loop.push(current_position);
+        let starting_position = self.map.find(|t| *t == Tile::Pipe('S')).unwrap();
+        let main_loop = bfs([starting_position], |p| connected(&self.map, *p), |_| false);
+        output(main_loop.distances().values().max().unwrap())
+    }
 
-        visited.insert(starting_position);
-        visited.insert(current_position);
+    fn part2(&mut self) -> Vec<String> {
+        output(OutputStatus::Unsolved)
+    }
 
-        let neigbors = [
-            (Point::new(0, 1), Direction::South),
-            (Point::new(1, 0), Direction::East),
-            (Point::new(-1, 0), Direction::West),
-            (Point::new(0, -1), Direction::North),
-        ];
+    fn assumptions(&self) -> Vec<Assumption> {
+        vec![Assumption::check(
+            "every tile next to S continues the loop",
+            start_off_loop(&self.map),
+        )]
+    }
 
-        'outer: loop {
-            let mut next_position = None;
-            for (point, direction) in neigbors.iter() {
-                let current_tile = self.map.get(&current_position).unwrap();
-                let next_loc = current_position + *point;
-                if main_loop.contains(&next_loc) {
-                    continue;
-                }
-                if let Some(t) = self.map.get(&next_loc) {
-                    if current_tile.can_connect(t, direction) {
-                        current_position = next_loc;
-                        next_position = Some(next_loc);
-                        main_loop.push(next_loc);
-                        visited.insert(next_loc);
-                        break;
-                    }
-                }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        // The loop is the outline of square blocks of tiles joined as a random
+        // tree, so that it is closed, never crosses itself and has enclosed
+        // tiles.
+        let block = rng.range(3, 6) as usize;
+        let blocks = (size.max(1) as f64).sqrt().ceil() as usize + 1;
+        let mut joined = vec![vec![[false; 4]; blocks]; blocks];
+        let mut in_tree = vec![vec![false; blocks]; blocks];
+        let mut tree = vec![(rng.below(blocks), rng.below(blocks))];
+        in_tree[tree[0].1][tree[0].0] = true;
+        let steps = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+        while tree.len() < size.max(1) {
+            let (x, y) = *rng.choose(&tree);
+            let side = rng.below(4);
+            let (nx, ny) = (x as i32 + steps[side].0, y as i32 + steps[side].1);
+            if nx < 0 || ny < 0 || nx >= blocks as i32 || ny >= blocks as i32 {
+                continue;
             }
-            if next_position.is_none() {
-                break 'outer;
+            let (nx, ny) = (nx as usize, ny as usize);
+            if !in_tree[ny][nx] {
+                in_tree[ny][nx] = true;
+                joined[y][x][side] = true;
+                joined[ny][nx][(side + 2) % 4] = true;
+                tree.push((nx, ny));
             }
         }
-        let position = main_loop
-            .iter()
-            .skip(1)
-            .zip(main_loop.iter().rev().skip(1))
-            .position(|(a, b)| a.y == b.y)
-            .unwrap();
 
-        dbg!(position);
+        let width = blocks * block;
+        let mut grid = vec![vec!['.'; width]; width];
+        for (x, y) in &tree {
+            let [north, east, south, west] = joined[*y][*x];
+            let last = block - 1;
+            for dy in 0..block {
+                for dx in 0..block {
+                    grid[y * block + dy][x * block + dx] = match (dx, dy) {
+                        (0, 0) => pipe(if north { 'N' } else { 'E' }, if west { 'W' } else { 'S' }),
+                        (dx, 0) if dx == last => {
+                            pipe(if north { 'N' } else { 'W' }, if east { 'E' } else { 'S' })
+                        }
+                        (0, dy) if dy == last => {
+                            pipe(if south { 'S' } else { 'E' }, if west { 'W' } else { 'N' })
+                        }
+                        (dx, dy) if dx == last && dy == last => {
+                            pipe(if south { 'S' } else { 'W' }, if east { 'E' } else { 'N' })
+                        }
+                        (_, 0) if !north => '-',
+                        (_, dy) if dy == last && !south => '-',
+                        (0, _) if !west => '|',
+                        (dx, _) if dx == last && !east => '|',
+                        _ => continue,
+                    };
+                }
+            }
+        }
 
-        output(main_loop.len() / 2)
+        let loop_tiles: Vec<(usize, usize)> = (0..width * width)
+            .map(|i| (i % width, i / width))
+            .filter(|(x, y)| grid[*y][*x] != '.')
+            .collect();
+        for row in grid.iter_mut() {
+            for tile in row.iter_mut().filter(|t| **t == '.') {
+                if rng.chance(0.3) {
+                    *tile = *rng.choose(&['|', '-', 'L', 'J', '7', 'F']);
+                }
+            }
+        }
+        let (x, y) = *rng.choose(&loop_tiles);
+        grid[y][x] = 'S';
+        Some(
+            grid.iter()
+                .map(|row| row.iter().collect::<String>() + "\n")
+                .collect(),
+        )
     }
+}
 
-    fn part2(&mut self) -> Vec<String> {
-        output(OutputStatus::Unsolved)
+/// The pipe connecting two of the directions `N`, `E`, `S` and `W`.
+fn pipe(a: char, b: char) -> char {
+    match (a.min(b), a.max(b)) {
+        ('N', 'S') => '|',
+        ('E', 'W') => '-',
+        ('E', 'N') => 'L',
+        ('N', 'W') => 'J',
+        ('S', 'W') => '7',
+        _ => 'F',
     }
 }
 
 // ---------------------------------------------------
 
-fn find_next_location_from_start(map: &Grid, current_position: Point<i32>) -> Point<i32> {
-    if let Some(t) = map.get(&(current_position + Point::new(0, 1))) {
-        if t == &Tile::Pipe('|') || t == &Tile::Pipe('F') || t == &Tile::Pipe('7') {
-            return current_position + Point::new(0, 1);
-        }
-    }
-    if let Some(t) = map.get(&(current_position + Point::new(1, 0))) {
-        if t == &Tile::Pipe('-') {
-            return current_position + Point::new(1, 0);
-        }
+/// Directions of the tiles a pipe connects to.
+fn openings(tile: &Tile) -> Vec<Direction> {
+    use Direction::*;
+    match tile {
+        Tile::Pipe('|') => vec![North, South],
+        Tile::Pipe('-') => vec![East, West],
+        Tile::Pipe('L') => vec![North, East],
+        Tile::Pipe('J') => vec![North, West],
+        Tile::Pipe('7') => vec![South, West],
+        Tile::Pipe('F') => vec![South, East],
+        _ => Vec::new(),
     }
-    if let Some(t) = map.get(&(current_position + Point::new(-1, 0))) {
-        if t == &Tile::Pipe('F') || t == &Tile::Pipe('L') {
-            return current_position + Point::new(-1, 0);
-        }
+}
+
+/// Whether the pipe at `from` has an opening towards `to`.
+fn points_at(map: &Grid, from: Point<i32>, to: Point<i32>) -> bool {
+    map.get(from)
+        .is_some_and(|t| openings(t).iter().any(|o| from + o.delta() == to))
+}
+
+fn describe(map: &Grid, position: Point<i32>) -> String {
+    let tile = match map.get(position) {
+        Some(Tile::Pipe(c)) => *c,
+        Some(Tile::Ground) | None => '.',
+    };
+    format!("`{tile}` at ({}, {})", position.x, position.y)
+}
+
+/// Why the tiles next to the start do not form the loop: unless exactly two
+/// of them point at the start, and the pipes leaving through one of them come
+/// back through the other.
+fn start_off_loop(map: &Grid) -> Option<String> {
+    let Some(start) = map.find(|t| *t == Tile::Pipe('S')) else {
+        return Some("there is no S".to_string());
+    };
+    let neighbors: Vec<Point<i32>> = start
+        .neighbors4()
+        .filter(|n| points_at(map, *n, start))
+        .collect();
+    if neighbors.len() != 2 {
+        let tiles: Vec<String> = neighbors.iter().map(|n| describe(map, *n)).collect();
+        return Some(format!(
+            "{} tiles point at S, not 2: [{}]",
+            neighbors.len(),
+            tiles.join(", ")
+        ));
     }
 
-    if let Some(t) = map.get(&(current_position + Point::new(0, -1))) {
-        if t == &Tile::Pipe('|') || t == &Tile::Pipe('J') || t == &Tile::Pipe('L') {
-            current_position + Point::new(-1, 0)
-        } else {
-            panic!("No tile found");
+    let (mut previous, mut current) = (start, neighbors[0]);
+    for _ in 0..map.width() * map.height() {
+        let Some(next) = map.get(current).and_then(|t| {
+            openings(t)
+                .into_iter()
+                .map(|o| current + o.delta())
+                .find(|n| *n != previous)
+        }) else {
+            break;
+        };
+        if next == start {
+            return None;
+        }
+        if !points_at(map, next, current) {
+            return Some(format!(
+                "the pipes leaving S through {} end at {}, next to {}",
+                describe(map, neighbors[0]),
+                describe(map, current),
+                describe(map, next),
+            ));
         }
-    } else {
-        panic!("No tile found");
+        (previous, current) = (current, next);
     }
+    Some(format!(
+        "the pipes leaving S through {} never come back",
+        describe(map, neighbors[0])
+    ))
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
+/// The tiles whose pipes connect to the one at `position`, where the start
+/// connects to any pipe pointing at it.
+fn connected(map: &Grid, position: Point<i32>) -> Vec<Point<i32>> {
+    let is_start = |p: Point<i32>| map.get(p) == Some(&Tile::Pipe('S'));
+    position
+        .neighbors4()
+        .filter(|n| {
+            (points_at(map, position, *n) || is_start(position))
+                && (points_at(map, *n, position) || is_start(*n))
+        })
+        .collect()
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// Ground is the empty tile of the sparse grid.
+#[derive(Debug, Default, PartialEq, Eq)]
 enum Tile {
+    #[default]
+    Ground,
     Pipe(char),
 }
 
-impl Tile {
-    fn can_connect(&self, other: &Tile, direction: &Direction) -> bool {
-        match direction {
-            Direction::North => match self {
-                Tile::Pipe('|') => {
-                    if other == &Tile::Pipe('|')
-                        || other == &Tile::Pipe('F')
-                        || other == &Tile::Pipe('7')
-                    {
-                        return true;
-                    }
-                }
-                Tile::Pipe('L') => {
-                    if other == &Tile::Pipe('|')
-                        || other == &Tile::Pipe('F')
-                        || other == &Tile::Pipe('7')
-                    {
-                        return true;
-                    }
-                }
-                Tile::Pipe('J') => {
-                    if other == &Tile::Pipe('|')
-                        || other == &Tile::Pipe('F')
-                        || other == &Tile::Pipe('7')
-                    {
-                        return true;
-                    }
-                }
-                _ => {}
-            },
-            Direction::South => match self {
-                Tile::Pipe('|') => {
-                    if other == &Tile::Pipe('|')
-                        || other == &Tile::Pipe('L')
-                        || other == &Tile::Pipe('J')
-                    {
-                        return true;
-                    }
-                }
-                Tile::Pipe('F') => {
-                    if other == &Tile::Pipe('|')
-                        || other == &Tile::Pipe('L')
-                        || other == &Tile::Pipe('J')
-                    {
-                        return true;
-                    }
-                }
-                Tile::Pipe('7') => {
-                    if other == &Tile::Pipe('|')
-                        || other == &Tile::Pipe('L')
-                        || other == &Tile::Pipe('J')
-                    {
-                        return true;
-                    }
-                }
-                _ => {}
-            },
-            Direction::East => match self {
-                Tile::Pipe('-') => {
-                    if other == &Tile::Pipe('-')
-                        || other == &Tile::Pipe('J')
-                        || other == &Tile::Pipe('7')
-                    {
-                        return true;
-                    }
-                }
-                Tile::Pipe('L') => {
-                    if other == &Tile::Pipe('-')
-                        || other == &Tile::Pipe('J')
-                        || other == &Tile::Pipe('7')
-                    {
-                        return true;
-                    }
-                }
-                Tile::Pipe('F') => {
-                    if other == &Tile::Pipe('-')
-                        || other == &Tile::Pipe('J')
-                        || other == &Tile::Pipe('7')
-                    {
-                        return true;
-                    }
-                }
-                _ => {}
-            },
-            Direction::West => match self {
-                Tile::Pipe('-') => {
-                    if other == &Tile::Pipe('-')
-                        || other == &Tile::Pipe('F')
-                        || other == &Tile::Pipe('L')
-                    {
-                        return true;
-                    }
-                }
-                Tile::Pipe('J') => {
-                    if other == &Tile::Pipe('-')
-                        || other == &Tile::Pipe('F')
-                        || other == &Tile::Pipe('L')
-                    {
-                        return true;
-                    }
-                }
-                Tile::Pipe('7') => {
-                    if other == &Tile::Pipe('-')
-                        || other == &Tile::Pipe('F')
-                        || other == &Tile::Pipe('L')
-                    {
-                        return true;
-                    }
-                }
-                _ => {}
-            },
-        }
-
-        false
-    }
-}
-
 // --------------------------------------
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day10-test.txt";
     const TEST_INPUT2: &str = "input/day10-test2.txt";
-    use aoc_2023_rust::Runner;
-
-    use crate::solutions::day10::Day10;
+    use aoc_2023_rust::solution_tests;
 
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day10::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "4")
-    }
-
-    #[test]
-    fn part1_test2_works() {
-        let mut day = Day10::new();
-        day.parse(Some(TEST_INPUT2));
-        let output = day.part1();
-        assert_eq!(output[0], "8")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day = Day10::new();
-        day.parse(None);
-        let _ = day.part1();
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day = Day10::new();
-        day.parse(Some(TEST_INPUT));
-        let _ = day.part2();
-    }
+    use crate::solutions::day10::{Day10, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day = Day10::new();
-        day.parse(None);
-        let _ = day.part2();
+    solution_tests! {
+        Day10, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("4");
+        part1_test2_works: example(TEST_INPUT2), part1 => answer("8");
+        part1_works: real, part1 => answer("6909");
+        part2_test_works: example(TEST_INPUT), part2 => unsolved;
+        part2_works: real, part2 => unsolved;
     }
 }
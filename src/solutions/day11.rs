@@ -1,6 +1,6 @@
-use std::{collections::HashMap, path::Path};
+use std::collections::HashMap;
 
-use aoc_2023_rust::{output, read_lines, read_lines_from_string, Point, Runner};
+use aoc_2023_rust::{output, Budget, Grid, Point, PuzzleInput, Rng, Runner};
 use itertools::Itertools;
 
 const INPUT: &str = "input/day11.txt";
@@ -21,33 +21,21 @@ impl Runner for Day11 {
         (2023, 11)
     }
 
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 80, 90)
+    }
+
     fn parse(&mut self, input: Option<&str>) {
-        let puzzle_input: Vec<String>;
-        if let Some(input) = input {
-            if Path::new(input).is_file() {
-                puzzle_input = read_lines(input);
-            } else {
-                puzzle_input = read_lines_from_string(input);
-            }
-        } else {
-            puzzle_input = read_lines(INPUT);
-        }
-        self.universe.width = puzzle_input.first().unwrap().len() as u64;
-        self.universe.height = puzzle_input.len() as u64;
-
-        let mut number = 1;
-        for (y, line) in puzzle_input.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                if c == '.' {
-                    continue;
-                }
-                self.universe.galaxies.push(Galaxy {
-                    location: Point::new(x as i32, y as i32),
-                    number,
-                });
-                number += 1;
-            }
-        }
+        let image = PuzzleInput::load(input, INPUT)
+            .grid(|c| Some(c != '.'))
+            .unwrap();
+        self.universe.width = image.width() as u64;
+        self.universe.height = image.height() as u64;
+        self.universe.galaxies = image
+            .find_all(|galaxy| *galaxy)
+            .zip(1..)
+            .map(|(location, number)| Galaxy { location, number })
+            .collect();
     }
 
     fn part1(&mut self) -> Vec<String> {
@@ -59,6 +47,24 @@ impl Runner for Day11 {
         self.universe.expand_universe(1_000_000);
         output(self.universe.calculate_total_distance_between_galaxies())
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(2);
+        let empty_rows: Vec<bool> = (0..size).map(|_| rng.chance(0.15)).collect();
+        let empty_columns: Vec<bool> = (0..size).map(|_| rng.chance(0.15)).collect();
+        let mut input = String::new();
+        for row in &empty_rows {
+            for column in &empty_columns {
+                input.push(if !row && !column && rng.chance(0.1) {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            input.push('\n');
+        }
+        Some(input)
+    }
 }
 
 // ---------------------------------------------------
@@ -82,7 +88,7 @@ impl Universe {
         for pairs in self.galaxies.iter().map(|g| g.number).combinations(2) {
             let a = self.galaxies.iter().find(|g| g.number == pairs[0]).unwrap();
             let b = self.galaxies.iter().find(|g| g.number == pairs[1]).unwrap();
-            total_distance += a.location.dist(b.location.x, b.location.y) as i64
+            total_distance += a.location.manhattan(&b.location) as i64
         }
         total_distance as u64
     }
@@ -134,38 +140,30 @@ impl Universe {
         }
     }
 
+    /// The rows and columns of the image without a galaxy, where galaxies
+    /// moved outside of it by an expansion no longer count.
     fn find_empty_spaces(&self) -> HashMap<String, Vec<u64>> {
-        let mut empty_spaces = HashMap::new();
-        for y in 0..self.height {
-            let mut empty = true;
-            for x in 0..self.width {
-                let p = Point::new(x as i32, y as i32);
-                if self.galaxies.iter().any(|g| g.location == p) {
-                    empty = false;
-                    break;
-                }
+        let mut image = Grid::new(self.width as usize, self.height as usize, false);
+        for galaxy in &self.galaxies {
+            if let Some(tile) = image.get_mut(galaxy.location) {
+                *tile = true;
             }
-            if empty {
+        }
+        let mut empty_spaces = HashMap::new();
+        for (y, row) in image.rows().enumerate() {
+            if !row.contains(&true) {
                 empty_spaces
                     .entry("rows".to_string())
                     .or_insert(Vec::new())
-                    .push(y);
+                    .push(y as u64);
             }
         }
-        for x in 0..self.width {
-            let mut empty = true;
-            for y in 0..self.height {
-                let p = Point::new(x as i32, y as i32);
-                if self.galaxies.iter().any(|g| g.location == p) {
-                    empty = false;
-                    break;
-                }
-            }
-            if empty {
+        for (x, column) in image.columns().enumerate() {
+            if !column.into_iter().any(|galaxy| *galaxy) {
                 empty_spaces
                     .entry("cols".to_string())
                     .or_insert(Vec::new())
-                    .push(x);
+                    .push(x as u64);
             }
         }
         empty_spaces
@@ -188,39 +186,32 @@ impl std::fmt::Display for Galaxy {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day11-test.txt";
-    use aoc_2023_rust::Runner;
-
-    use crate::solutions::day11::Day11;
+    use aoc_2023_rust::{assert_snapshot, solution_tests, Runner};
 
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day11::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "374")
-    }
+    use crate::solutions::day11::{Day11, INPUT};
 
-    #[test]
-    fn part1_works() {
-        let mut day = Day11::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "10154062")
+    solution_tests! {
+        Day11, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("374");
+        part1_works: real, part1 => answer("10154062");
+        part2_test_works: example(TEST_INPUT), part2 => answer("82000210");
+        part2_works: real, part2 => answer("553083047914");
     }
 
     #[test]
-    fn part2_test_works() {
+    fn universe_snapshot() {
         let mut day = Day11::new();
         day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "82000210")
-    }
-
-    #[test]
-    fn part2_works() {
-        let mut day = Day11::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "553083047914")
+        let mut render = String::new();
+        for expansion in [1, 2] {
+            if expansion > 1 {
+                day.universe.expand_universe(expansion);
+            }
+            render.push_str(&format!("Expansion rate {expansion}:\n{}", day.universe));
+            for galaxy in &day.universe.galaxies {
+                render.push_str(&galaxy.to_string());
+            }
+        }
+        assert_snapshot("day11_universe", &render);
     }
 }
@@ -1,10 +1,7 @@
-use std::path::Path;
-
-use aoc_2023_rust::{output, read_lines, read_lines_from_string, OutputStatus, Runner};
+use aoc_2023_rust::{output, Budget, Memo, PuzzleInput, Rng, Runner};
 use itertools::Itertools;
 
-//const INPUT: &str = "input/day12.txt";
-const TEST_INPUT: &str = "input/day12-test.txt";
+const INPUT: &str = "input/day12.txt";
 
 #[derive(Debug, Default)]
 pub struct Day12 {
@@ -22,47 +19,68 @@ impl Runner for Day12 {
         (2023, 12)
     }
 
-    fn parse(&mut self, input: Option<&str>) {
-        let puzzle_input: Vec<String>;
-        if let Some(input) = input {
-            if Path::new(input).is_file() {
-                puzzle_input = read_lines(input);
-            } else {
-                puzzle_input = read_lines_from_string(input);
-            }
-        } else {
-            puzzle_input = read_lines(TEST_INPUT);
-        }
+    fn budget(&self) -> Budget {
+        Budget::millis(2, 5, 80)
+    }
 
-        for line in puzzle_input {
-            self.springs.push(Spring::from(line.as_str()));
+    fn parse(&mut self, input: Option<&str>) {
+        for line in PuzzleInput::load(input, INPUT).lines() {
+            self.springs.push(Spring::from(line.text));
         }
     }
 
     fn part1(&mut self) -> Vec<String> {
-        output(OutputStatus::Unsolved)
+        let mut memo = Memo::new();
+        output(
+            self.springs
+                .iter()
+                .map(|s| s.arrangements(&mut memo))
+                .sum::<u64>(),
+        )
     }
 
     fn part2(&mut self) -> Vec<String> {
-        output(OutputStatus::Unsolved)
+        let mut memo = Memo::new();
+        output(
+            self.springs
+                .iter()
+                .map(|s| s.unfold().arrangements(&mut memo))
+                .sum::<u64>(),
+        )
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size.max(1) {
+            let mut record = String::new();
+            let mut groups = Vec::new();
+            for _ in 0..rng.range(1, 6) {
+                record.push_str(&".".repeat(rng.range(0, 3) as usize));
+                let group = rng.range(1, 5);
+                record.push_str(&"#".repeat(group as usize));
+                record.push('.');
+                groups.push(group.to_string());
+            }
+            let record: String = record
+                .chars()
+                .map(|c| if rng.chance(0.4) { '?' } else { c })
+                .collect();
+            input.push_str(&format!("{record} {}\n", groups.join(",")));
+        }
+        Some(input)
     }
 }
 
 // ---------------------------------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 enum SpringCondition {
     Operational,
     Damaged,
+    #[default]
     Unknown,
 }
 
-impl Default for SpringCondition {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 #[derive(Debug, Default)]
 struct Spring {
     record: SpringRecord,
@@ -97,6 +115,62 @@ impl Spring {
             damaged_springs: vec![],
         }
     }
+
+    /// The record and the groups repeated five times, the records joined by
+    /// unknown springs.
+    fn unfold(&self) -> Spring {
+        let mut data = self.record.data.clone();
+        for _ in 1..5 {
+            data.push(SpringCondition::Unknown);
+            data.extend(&self.record.data);
+        }
+        Spring {
+            record: SpringRecord { data },
+            damaged_springs: self.damaged_springs.repeat(5),
+        }
+    }
+
+    /// The memo is keyed on positions in this record, so it is cleared
+    /// first.
+    fn arrangements(&self, memo: &mut Memo<(usize, usize), u64>) -> u64 {
+        memo.clear();
+        arrangements(memo, &self.record.data, &self.damaged_springs, 0, 0)
+    }
+}
+
+/// Ways to place the groups from `group` on in the record from `position`
+/// on, where a group is a run of damaged springs ended by an operational one
+/// or the end of the record.
+fn arrangements(
+    memo: &mut Memo<(usize, usize), u64>,
+    record: &[SpringCondition],
+    groups: &[u32],
+    position: usize,
+    group: usize,
+) -> u64 {
+    memo.get_or_compute((position, group), |memo| {
+        let Some(condition) = record.get(position) else {
+            return (group == groups.len()) as u64;
+        };
+        let mut ways = 0;
+        if *condition != SpringCondition::Damaged {
+            ways += arrangements(memo, record, groups, position + 1, group);
+        }
+        if *condition != SpringCondition::Operational && group < groups.len() {
+            let end = position + groups[group] as usize;
+            let fits = end <= record.len()
+                && record[position..end]
+                    .iter()
+                    .all(|c| *c != SpringCondition::Operational)
+                && record.get(end) != Some(&SpringCondition::Damaged);
+            if fits {
+                // Skips the operational spring after the group too.
+                let next = (end + 1).min(record.len());
+                ways += arrangements(memo, record, groups, next, group + 1);
+            }
+        }
+        ways
+    })
 }
 
 #[derive(Debug, Default)]
@@ -108,39 +182,15 @@ struct SpringRecord {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day12-test.txt";
-    use aoc_2023_rust::Runner;
-
-    use crate::solutions::day12::Day12;
-
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day12::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "21")
-    }
+    use aoc_2023_rust::solution_tests;
 
-    #[test]
-    fn part1_works() {
-        let mut day = Day12::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "10154062")
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day = Day12::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "82000210")
-    }
+    use crate::solutions::day12::{Day12, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day = Day12::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "553083047914")
+    solution_tests! {
+        Day12, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("21");
+        part1_works: real, part1 => answer("8270");
+        part2_test_works: example(TEST_INPUT), part2 => answer("525152");
+        part2_works: real, part2 => answer("204640299929836");
     }
 }
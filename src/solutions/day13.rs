@@ -1,6 +1,6 @@
-use std::{collections::HashMap, fs::read_to_string, vec};
+use std::{collections::HashMap, vec};
 
-use aoc_2023_rust::{output, OutputStatus, Point, Runner};
+use aoc_2023_rust::{output, Budget, Grid, OutputStatus, Point, PuzzleInput, Rng, Runner};
 use itertools::Itertools;
 
 const INPUT: &str = "input/day13.txt";
@@ -21,13 +21,13 @@ impl Runner for Day13 {
         (2023, 13)
     }
 
-    fn parse(&mut self, _: Option<&str>) {
-        let input = read_to_string(INPUT)
-            .expect("unable to open file")
-            .trim()
-            .to_owned();
-        for pattern in input.split("\n\n").collect::<Vec<_>>() {
-            self.patterns.push(Pattern::from(pattern));
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 2, 1)
+    }
+
+    fn parse(&mut self, input: Option<&str>) {
+        for pattern in PuzzleInput::load(input, INPUT).sections() {
+            self.patterns.push(Pattern::from(&pattern));
         }
     }
 
@@ -47,6 +47,58 @@ impl Runner for Day13 {
     fn part2(&mut self) -> Vec<String> {
         output(OutputStatus::Unsolved)
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut patterns = Vec::new();
+        while patterns.len() < size.max(1) {
+            let (width, height) = (rng.range(5, 18) as usize, rng.range(5, 18) as usize);
+            let mut grid = Grid::from_rows(
+                (0..height)
+                    .map(|_| {
+                        (0..width)
+                            .map(|_| {
+                                if rng.chance(0.5) {
+                                    Type::Rock
+                                } else {
+                                    Type::Ash
+                                }
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            );
+            // Mirror the rows or the columns around a random line.
+            let vertical = rng.chance(0.5);
+            if vertical {
+                grid = grid.transpose();
+            }
+            let line = rng.range(1, grid.height() as u64) as usize;
+            for i in 0..line.min(grid.height() - line) {
+                for x in 0..grid.width() as i32 {
+                    let mirrored = grid[Point::new(x, (line - 1 - i) as i32)];
+                    grid[Point::new(x, (line + i) as i32)] = mirrored;
+                }
+            }
+            if vertical {
+                grid = grid.transpose();
+            }
+            // Keep only patterns with a single line of reflection.
+            let lines = [grid.clone(), grid.transpose()]
+                .iter()
+                .map(|grid| (1..grid.height()).filter(|l| reflects_at(grid, *l)).count())
+                .sum::<usize>();
+            if lines == 1 {
+                patterns.push(grid.to_string().trim_end().to_string());
+            }
+        }
+        Some(patterns.join("\n\n") + "\n")
+    }
+}
+
+/// Whether every pair of rows around the line above row `line` mirror
+/// each other, up to the nearest edge.
+fn reflects_at(grid: &Grid<Type>, line: usize) -> bool {
+    (0..line.min(grid.height() - line)).all(|i| grid.row(line + i) == grid.row(line - 1 - i))
 }
 
 // ---------------------------------------------------
@@ -57,23 +109,18 @@ enum Type {
     Rock,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct PatternData {
-    point: Point<i32>,
-    p_type: Type,
-}
-
-impl PatternData {
-    fn new(point: Point<i32>, p_type: Type) -> Self {
-        Self { point, p_type }
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Ash => write!(f, "."),
+            Type::Rock => write!(f, "#"),
+        }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 struct Pattern {
-    data: Vec<Vec<PatternData>>,
-    width: u32,
-    height: u32,
+    grid: Grid<Type>,
 }
 
 impl Pattern {
@@ -81,11 +128,9 @@ impl Pattern {
         let mut reflections = HashMap::new();
         reflections.insert("horizontal".to_string(), Vec::new());
         reflections.insert("vertical".to_string(), Vec::new());
-        for i in 0..self.data.len() - 1 {
-            for j in i + 1..self.data.len() {
-                if self.data[i].iter().map(|p| p.p_type).collect_vec()
-                    == self.data[j].iter().map(|p| p.p_type).collect_vec()
-                {
+        for i in 0..self.grid.height() - 1 {
+            for j in i + 1..self.grid.height() {
+                if self.grid.row(i) == self.grid.row(j) {
                     reflections
                         .entry("horizontal".to_string())
                         .or_insert_with(Vec::new)
@@ -93,21 +138,13 @@ impl Pattern {
                 }
             }
         }
-        for i in 0..self.width - 1 {
-            for j in i + 1..self.width {
-                let mut data1 = Vec::new();
-                let mut data2 = Vec::new();
-                for row in &self.data {
-                    data1.push(&row[i as usize]);
-                    data2.push(&row[j as usize]);
-                }
-                if data1.iter().map(|p| p.p_type).collect_vec()
-                    == data2.iter().map(|p| p.p_type).collect_vec()
-                {
+        for i in 0..self.grid.width() - 1 {
+            for j in i + 1..self.grid.width() {
+                if self.grid.column(i).collect_vec() == self.grid.column(j).collect_vec() {
                     reflections
                         .entry("vertical".to_string())
                         .or_insert_with(Vec::new)
-                        .push((i, j));
+                        .push((i as u32, j as u32));
                 }
             }
         }
@@ -148,7 +185,7 @@ impl Pattern {
                     .unwrap_or(&0),
             );
         }
-        return (
+        (
             1,
             *reflections
                 .get("horizontal")
@@ -160,28 +197,20 @@ impl Pattern {
                 .iter()
                 .max()
                 .unwrap_or(&0),
-        );
+        )
     }
 }
 
-impl From<&str> for Pattern {
-    fn from(input: &str) -> Self {
-        let mut pattern = Self::default();
-        for (y, line) in input.lines().enumerate() {
-            let mut data = Vec::new();
-            for (x, ch) in line.chars().enumerate() {
-                let point = Point::new(x as i32, y as i32);
-                match ch {
-                    '#' => data.push(PatternData::new(point, Type::Rock)),
-                    '.' => data.push(PatternData::new(point, Type::Ash)),
-                    _ => panic!("invalid input"),
-                };
-            }
-            pattern.data.push(data);
-        }
-        pattern.width = input.lines().next().unwrap().len() as u32;
-        pattern.height = input.lines().count() as u32;
-        pattern
+impl From<&PuzzleInput> for Pattern {
+    fn from(input: &PuzzleInput) -> Self {
+        let grid = input
+            .grid(|ch| match ch {
+                '#' => Some(Type::Rock),
+                '.' => Some(Type::Ash),
+                _ => None,
+            })
+            .unwrap();
+        Self { grid }
     }
 }
 
@@ -189,39 +218,15 @@ impl From<&str> for Pattern {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day13-test.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::solution_tests;
 
-    use crate::solutions::day13::Day13;
-
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day13::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "405")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day = Day13::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "4")
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day = Day13::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "82000210")
-    }
+    use crate::solutions::day13::{Day13, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day = Day13::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "553083047914")
+    solution_tests! {
+        Day13, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("405");
+        part1_works: real, part1 => wrong("37381");
+        part2_test_works: example(TEST_INPUT), part2 => unsolved;
+        part2_works: real, part2 => unsolved;
     }
 }
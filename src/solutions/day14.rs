@@ -1,6 +1,4 @@
-use std::path::Path;
-
-use aoc_2023_rust::{output, read_lines, read_lines_from_string, Runner};
+use aoc_2023_rust::{output, Budget, Grid, Point, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day14.txt";
 
@@ -20,34 +18,22 @@ impl Runner for Day14 {
         (2023, 14)
     }
 
-    fn parse(&mut self, input: Option<&str>) {
-        let puzzle_input: Vec<String>;
-        if let Some(input) = input {
-            if Path::new(input).is_file() {
-                puzzle_input = read_lines(input);
-            } else {
-                puzzle_input = read_lines_from_string(input);
-            }
-        } else {
-            puzzle_input = read_lines(INPUT);
-        }
-
-        self.reflector.width = puzzle_input[0].len() as u32;
-        self.reflector.height = puzzle_input.len() as u32;
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 1, 450)
+    }
 
-        for (_, line) in puzzle_input.iter().enumerate() {
-            let mut row = Vec::new();
-            for (_, c) in line.chars().enumerate() {
+    fn parse(&mut self, input: Option<&str>) {
+        self.reflector.dishes = PuzzleInput::load(input, INPUT)
+            .grid(|c| {
                 let dish_type = match c {
                     'O' => DishType::RoundedRock,
                     '.' => DishType::Empty,
                     '#' => DishType::CubeRock,
-                    _ => panic!("Unknown dish type"),
+                    _ => return None,
                 };
-                row.push(Dish { dish_type });
-            }
-            self.reflector.dishes.push(row);
-        }
+                Some(Dish { dish_type })
+            })
+            .unwrap();
     }
 
     fn part1(&mut self) -> Vec<String> {
@@ -60,6 +46,21 @@ impl Runner for Day14 {
         }
         output(self.reflector.calculate_load())
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size.max(1) {
+            for _ in 0..size.max(1) {
+                input.push(match rng.below(10) {
+                    0..=1 => 'O',
+                    2 => '#',
+                    _ => '.',
+                });
+            }
+            input.push('\n');
+        }
+        Some(input)
+    }
 }
 
 // ---------------------------------------------------
@@ -76,42 +77,35 @@ struct Dish {
     dish_type: DishType,
 }
 
+impl std::fmt::Display for Dish {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self.dish_type {
+            DishType::RoundedRock => 'O',
+            DishType::CubeRock => '#',
+            DishType::Empty => '.',
+        };
+        write!(f, "{}", c)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 struct Reflector {
-    dishes: Vec<Vec<Dish>>,
-    width: u32,
-    height: u32,
+    dishes: Grid<Dish>,
 }
 
 impl std::fmt::Display for Reflector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in &self.dishes {
-            for dish in row {
-                let c = match dish.dish_type {
-                    DishType::RoundedRock => 'O',
-                    DishType::CubeRock => '#',
-                    DishType::Empty => '.',
-                };
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        write!(f, "{}", self.dishes)
     }
 }
 
 impl Reflector {
     fn calculate_load(&self) -> u32 {
-        let mut total_load = 0;
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let dish = self.dishes[y as usize][x as usize];
-                if dish.dish_type == DishType::RoundedRock {
-                    total_load += self.height - y;
-                }
-            }
-        }
-        total_load
+        let height = self.dishes.height() as u32;
+        self.dishes
+            .find_all(|dish| dish.dish_type == DishType::RoundedRock)
+            .map(|position| height - position.y as u32)
+            .sum()
     }
 
     fn cycle(&mut self) {
@@ -122,128 +116,54 @@ impl Reflector {
     }
 
     fn slide_to_north(&mut self) {
-        for y in 1..self.height {
-            for x in 0..self.width {
-                let dish = self.dishes[y as usize][x as usize];
-                let mut next_y: i32 = y as i32;
-                if dish.dish_type == DishType::RoundedRock {
-                    loop {
-                        if next_y > 0 {
-                            let dish = self.dishes[(next_y - 1) as usize][x as usize];
-                            if dish.dish_type == DishType::Empty {
-                                next_y -= 1;
-                                continue;
-                            } else {
-                                break;
-                            }
-                        } else {
-                            break;
-                        }
-                    }
-                }
-                if next_y != y as i32 {
-                    self.dishes[y as usize][x as usize] = Dish {
-                        dish_type: DishType::Empty,
-                    };
-                    self.dishes[next_y as usize][x as usize] = Dish {
-                        dish_type: DishType::RoundedRock,
-                    };
-                }
-            }
-        }
+        self.slide(Point::new(0, -1));
     }
 
     fn slide_to_west(&mut self) {
-        for y in 0..self.height {
-            for x in 1..self.width {
-                let dish = self.dishes[y as usize][x as usize];
-                let mut next_x: i32 = x as i32;
-                if dish.dish_type == DishType::RoundedRock {
-                    loop {
-                        if next_x > 0 {
-                            let dish = self.dishes[y as usize][(next_x - 1) as usize];
-                            if dish.dish_type == DishType::Empty {
-                                next_x -= 1;
-                                continue;
-                            } else {
-                                break;
-                            }
-                        } else {
-                            break;
-                        }
-                    }
-                }
-                if next_x != x as i32 {
-                    self.dishes[y as usize][x as usize] = Dish {
-                        dish_type: DishType::Empty,
-                    };
-                    self.dishes[y as usize][next_x as usize] = Dish {
-                        dish_type: DishType::RoundedRock,
-                    };
-                }
-            }
-        }
+        self.slide(Point::new(-1, 0));
     }
 
     fn slide_to_south(&mut self) {
-        for y in (0..self.height - 1).rev() {
-            for x in 0..self.width {
-                let dish = self.dishes[y as usize][x as usize];
-                let mut next_y: i32 = y as i32;
-                if dish.dish_type == DishType::RoundedRock {
-                    loop {
-                        if next_y < self.height as i32 - 1 {
-                            let dish = self.dishes[(next_y + 1) as usize][x as usize];
-                            if dish.dish_type == DishType::Empty {
-                                next_y += 1;
-                                continue;
-                            } else {
-                                break;
-                            }
-                        } else {
-                            break;
-                        }
-                    }
-                }
-                if next_y != y as i32 {
-                    self.dishes[y as usize][x as usize] = Dish {
-                        dish_type: DishType::Empty,
-                    };
-                    self.dishes[next_y as usize][x as usize] = Dish {
-                        dish_type: DishType::RoundedRock,
-                    };
-                }
-            }
-        }
+        self.slide(Point::new(0, 1));
     }
 
     fn slide_to_east(&mut self) {
-        for y in 0..self.height {
-            for x in (0..self.width - 1).rev() {
-                let dish = self.dishes[y as usize][x as usize];
-                let mut next_x: i32 = x as i32;
-                if dish.dish_type == DishType::RoundedRock {
-                    loop {
-                        if next_x < self.width as i32 - 1 {
-                            let dish = self.dishes[y as usize][(next_x + 1) as usize];
-                            if dish.dish_type == DishType::Empty {
-                                next_x += 1;
-                                continue;
-                            } else {
-                                break;
-                            }
-                        } else {
-                            break;
+        self.slide(Point::new(1, 0));
+    }
+
+    /// Rolls every rounded rock as far as it goes towards `direction`.
+    /// Each row or column is walked from the side the rocks roll to,
+    /// keeping track of the next free slot a rock can settle in.
+    fn slide(&mut self, direction: Point<i32>) {
+        let (width, height) = (self.dishes.width(), self.dishes.height());
+        let (lanes, length) = if direction.x == 0 {
+            (width, height)
+        } else {
+            (height, width)
+        };
+        let tiles = self.dishes.tiles_mut();
+        for lane in 0..lanes {
+            // Where the lane starts on the side the rocks roll to, and how
+            // far apart its tiles are.
+            let (origin, stride) = match (direction.x, direction.y) {
+                (0, -1) => (lane, width as isize),
+                (0, _) => (lane + (height - 1) * width, -(width as isize)),
+                (-1, _) => (lane * width, 1),
+                _ => (lane * width + width - 1, -1),
+            };
+            let at = |step: usize| origin.wrapping_add_signed(stride * step as isize);
+            let mut free = 0;
+            for step in 0..length {
+                match tiles[at(step)].dish_type {
+                    DishType::CubeRock => free = step + 1,
+                    DishType::RoundedRock => {
+                        if free != step {
+                            tiles[at(free)].dish_type = DishType::RoundedRock;
+                            tiles[at(step)].dish_type = DishType::Empty;
                         }
+                        free += 1;
                     }
-                }
-                if next_x != x as i32 {
-                    self.dishes[y as usize][x as usize] = Dish {
-                        dish_type: DishType::Empty,
-                    };
-                    self.dishes[y as usize][next_x as usize] = Dish {
-                        dish_type: DishType::RoundedRock,
-                    };
+                    DishType::Empty => {}
                 }
             }
         }
@@ -254,39 +174,27 @@ impl Reflector {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day14-test.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::{assert_snapshot, solution_tests, Runner};
 
-    use crate::solutions::day14::Day14;
-
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day14::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "136")
-    }
+    use crate::solutions::day14::{Day14, INPUT};
 
-    #[test]
-    fn part1_works() {
-        let mut day = Day14::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "105784")
+    solution_tests! {
+        Day14, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("136");
+        part1_works: real, part1 => answer("105784");
+        part2_test_works: example(TEST_INPUT), part2 => answer("64");
+        part2_works: real, part2 => answer("91286");
     }
 
     #[test]
-    fn part2_test_works() {
+    fn cycles_snapshot() {
         let mut day = Day14::new();
         day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "64")
-    }
-
-    #[test]
-    fn part2_works() {
-        let mut day = Day14::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "91286")
+        let mut states = format!("Initial:\n{}", day.reflector);
+        for cycle in 1..=3 {
+            day.reflector.cycle();
+            states.push_str(&format!("\nAfter {cycle} cycle(s):\n{}", day.reflector));
+        }
+        assert_snapshot("day14_cycles", &states);
     }
 }
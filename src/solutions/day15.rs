@@ -1,9 +1,6 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    path::Path,
-};
+use std::collections::{HashMap, VecDeque};
 
-use aoc_2023_rust::{output, read_lines, read_lines_from_string, Runner};
+use aoc_2023_rust::{output, Budget, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day15.txt";
 
@@ -23,19 +20,14 @@ impl Runner for Day15 {
         (2023, 15)
     }
 
-    fn parse(&mut self, input: Option<&str>) {
-        let puzzle_input: Vec<String>;
-        if let Some(input) = input {
-            if Path::new(input).is_file() {
-                puzzle_input = read_lines(input);
-            } else {
-                puzzle_input = read_lines_from_string(input);
-            }
-        } else {
-            puzzle_input = read_lines(INPUT);
-        }
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 1, 2)
+    }
 
-        self.init_sequence = InitializationSequence::from(puzzle_input[0].as_str());
+    fn parse(&mut self, input: Option<&str>) {
+        let input = PuzzleInput::load(input, INPUT);
+        let (sequence, _) = input.split_header().unwrap();
+        self.init_sequence = InitializationSequence::from(sequence.text);
     }
 
     fn part1(&mut self) -> Vec<String> {
@@ -55,6 +47,27 @@ impl Runner for Day15 {
         });
         output(facility.calculate_focusing_power())
     }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let labels: Vec<String> = (0..size.clamp(1, 50))
+            .map(|_| {
+                (0..rng.range(2, 7))
+                    .map(|_| (b'a' + rng.below(26) as u8) as char)
+                    .collect()
+            })
+            .collect();
+        let steps: Vec<String> = (0..size.max(1) * 4)
+            .map(|_| {
+                let label = rng.choose(&labels);
+                if rng.chance(0.3) {
+                    format!("{label}-")
+                } else {
+                    format!("{label}={}", rng.range(1, 10))
+                }
+            })
+            .collect();
+        Some(steps.join(",") + "\n")
+    }
 }
 
 // ---------------------------------------------------
@@ -212,39 +225,15 @@ impl From<&Step> for Len {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day15-test.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::solution_tests;
 
-    use crate::solutions::day15::Day15;
-
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day15::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "1320")
-    }
-
-    #[test]
-    fn part1_works() {
-        let mut day = Day15::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "516804")
-    }
-
-    #[test]
-    fn part2_test_works() {
-        let mut day = Day15::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "145")
-    }
+    use crate::solutions::day15::{Day15, INPUT};
 
-    #[test]
-    fn part2_works() {
-        let mut day = Day15::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "231844")
+    solution_tests! {
+        Day15, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("1320");
+        part1_works: real, part1 => answer("516804");
+        part2_test_works: example(TEST_INPUT), part2 => answer("145");
+        part2_works: real, part2 => answer("231844");
     }
 }
@@ -1,6 +1,6 @@
-use std::{collections::HashSet, path::Path};
+use std::collections::HashSet;
 
-use aoc_2023_rust::{output, read_lines, read_lines_from_string, Point, Runner};
+use aoc_2023_rust::{output, Budget, Direction, Grid, Point, PuzzleInput, Rng, Runner};
 
 const INPUT: &str = "input/day16.txt";
 
@@ -20,87 +20,89 @@ impl Runner for Day16 {
         (2023, 16)
     }
 
-    fn parse(&mut self, input: Option<&str>) {
-        let puzzle_input: Vec<String>;
-        if let Some(input) = input {
-            if Path::new(input).is_file() {
-                puzzle_input = read_lines(input);
-            } else {
-                puzzle_input = read_lines_from_string(input);
-            }
-        } else {
-            puzzle_input = read_lines(INPUT);
-        }
-
-        self.contraption.width = puzzle_input[0].len() as u16;
-        self.contraption.height = puzzle_input.len() as u16;
+    fn budget(&self) -> Budget {
+        Budget::millis(1, 10, 3000)
+    }
 
-        for (y, line) in puzzle_input.iter().enumerate() {
-            let mut row = Vec::new();
-            for (x, c) in line.chars().enumerate() {
-                let position = Point::new(x as i16, y as i16);
+    fn parse(&mut self, input: Option<&str>) {
+        self.contraption.tiles = PuzzleInput::load(input, INPUT)
+            .grid(|c| {
                 let contraption_type = match c {
                     '.' => ContraptionType::Empty,
                     '/' => ContraptionType::MirrorRight,
                     '\\' => ContraptionType::MirrorLeft,
                     '|' => ContraptionType::SplitterV,
                     '-' => ContraptionType::SplitterH,
-                    _ => panic!("Unknown contraption type"),
+                    _ => return None,
                 };
-                row.push(Tile {
-                    position,
+                Some(Tile {
                     contraption_type,
                     is_energized: false,
-                });
-            }
-            self.contraption.tiles.push(row);
-        }
+                })
+            })
+            .unwrap();
     }
 
     fn part1(&mut self) -> Vec<String> {
         output(
             self.contraption
-                .run(vec![(Point::new(0, 0), vec![Direction::Right])]),
+                .run(vec![(Point::new(0, 0), vec![Direction::East])]),
         )
     }
     fn part2(&mut self) -> Vec<String> {
-        output(self.contraption.run(generate_edge_tiles(
-            self.contraption.width,
-            self.contraption.height,
-        )))
+        let tiles = &self.contraption.tiles;
+        let edge_tiles = generate_edge_tiles(tiles.width() as u16, tiles.height() as u16);
+        output(self.contraption.run(edge_tiles))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size.max(1) {
+            for _ in 0..size.max(1) {
+                input.push(match rng.below(20) {
+                    0 => '/',
+                    1 => '\\',
+                    2 => '|',
+                    3 => '-',
+                    _ => '.',
+                });
+            }
+            input.push('\n');
+        }
+        Some(input)
     }
 }
 
 // ---------------------------------------------------
 
-fn generate_edge_tiles(width: u16, height: u16) -> Vec<(Point<i16>, Vec<Direction>)> {
+fn generate_edge_tiles(width: u16, height: u16) -> Vec<(Point<i32>, Vec<Direction>)> {
     let mut edge_tiles = Vec::new();
     for y in [0, height - 1].iter() {
         for x in 0..width {
             if *y == 0 && x == 0 {
                 edge_tiles.push((
-                    Point::new(x as i16, *y as i16),
-                    vec![Direction::Right, Direction::Down],
+                    Point::new(x as i32, *y as i32),
+                    vec![Direction::East, Direction::South],
                 ));
             } else if *y == 0 && x == width - 1 {
                 edge_tiles.push((
-                    Point::new(x as i16, *y as i16),
-                    vec![Direction::Left, Direction::Down],
+                    Point::new(x as i32, *y as i32),
+                    vec![Direction::West, Direction::South],
                 ));
             } else if *y == height - 1 && x == 0 {
                 edge_tiles.push((
-                    Point::new(x as i16, *y as i16),
-                    vec![Direction::Right, Direction::Up],
+                    Point::new(x as i32, *y as i32),
+                    vec![Direction::East, Direction::North],
                 ));
             } else if *y == height - 1 && x == width - 1 {
                 edge_tiles.push((
-                    Point::new(x as i16, *y as i16),
-                    vec![Direction::Left, Direction::Up],
+                    Point::new(x as i32, *y as i32),
+                    vec![Direction::West, Direction::North],
                 ));
             } else if *y == 0 {
-                edge_tiles.push((Point::new(x as i16, *y as i16), vec![Direction::Down]));
+                edge_tiles.push((Point::new(x as i32, *y as i32), vec![Direction::South]));
             } else {
-                edge_tiles.push((Point::new(x as i16, *y as i16), vec![Direction::Up]));
+                edge_tiles.push((Point::new(x as i32, *y as i32), vec![Direction::North]));
             }
         }
     }
@@ -108,23 +110,15 @@ fn generate_edge_tiles(width: u16, height: u16) -> Vec<(Point<i16>, Vec<Directio
     for x in [0, width - 1].iter() {
         for y in 1..height - 1 {
             if *x == 0 {
-                edge_tiles.push((Point::new(*x as i16, y as i16), vec![Direction::Right]));
+                edge_tiles.push((Point::new(*x as i32, y as i32), vec![Direction::East]));
             } else {
-                edge_tiles.push((Point::new(*x as i16, y as i16), vec![Direction::Left]));
+                edge_tiles.push((Point::new(*x as i32, y as i32), vec![Direction::West]));
             }
         }
     }
     edge_tiles
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum ContraptionType {
     Empty,
@@ -136,59 +130,34 @@ enum ContraptionType {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Tile {
-    position: Point<i16>,
     contraption_type: ContraptionType,
     is_energized: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Beam {
-    position: Point<i16>,
+    position: Point<i32>,
     direction: Direction,
 }
 
 impl Beam {
-    fn next_position(&self, width: u16, height: u16) -> Option<Point<i16>> {
-        let mut next_position = self.position;
-        match self.direction {
-            Direction::Up => {
-                next_position.y -= 1;
-            }
-            Direction::Down => {
-                next_position.y += 1;
-            }
-            Direction::Left => {
-                next_position.x -= 1;
-            }
-            Direction::Right => {
-                next_position.x += 1;
-            }
-        }
-        if next_position.x < 0
-            || next_position.x >= width as i16
-            || next_position.y < 0
-            || next_position.y >= height as i16
-        {
-            None
-        } else {
-            Some(next_position)
-        }
+    fn next_position(&self, tiles: &Grid<Tile>) -> Option<Point<i32>> {
+        let position = self.position + self.direction.delta();
+        tiles.contains(position).then_some(position)
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 struct Contraption {
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
     beams: Vec<Beam>,
-    width: u16,
-    height: u16,
 }
 
 impl std::fmt::Display for Contraption {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut output = String::new();
-        for row in self.tiles.iter() {
-            for tile in row.iter() {
+        for row in self.tiles.rows() {
+            for tile in row {
                 if tile.is_energized {
                     output.push('#');
                 } else {
@@ -208,15 +177,15 @@ impl std::fmt::Display for Contraption {
 }
 
 impl Contraption {
-    fn get_tile_type(&self, position: Point<i16>) -> ContraptionType {
-        self.tiles[position.y as usize][position.x as usize].contraption_type
+    fn get_tile_type(&self, position: Point<i32>) -> ContraptionType {
+        self.tiles[position].contraption_type
     }
 
-    fn energize_tile(&mut self, position: Point<i16>) {
-        self.tiles[position.y as usize][position.x as usize].is_energized = true;
+    fn energize_tile(&mut self, position: Point<i32>) {
+        self.tiles[position].is_energized = true;
     }
 
-    fn add_first_beam(&mut self, position: Point<i16>, direction: Direction) {
+    fn add_first_beam(&mut self, position: Point<i32>, direction: Direction) {
         self.beams.push(Beam {
             position,
             direction,
@@ -225,150 +194,100 @@ impl Contraption {
 
     fn calculate_energized_tiles(&self) -> u32 {
         self.tiles
-            .iter()
+            .rows()
             .flatten()
             .filter(|t| t.is_energized)
             .count() as u32
     }
 
     fn revert_energized_tiles(&mut self) {
-        for row in self.tiles.iter_mut() {
-            for tile in row.iter_mut() {
-                tile.is_energized = false;
-            }
-        }
+        self.tiles = self.tiles.map(|tile| Tile {
+            is_energized: false,
+            ..tile.clone()
+        });
     }
 
-    fn run(&mut self, starting_tiles: Vec<(Point<i16>, Vec<Direction>)>) -> u32 {
+    fn run(&mut self, starting_tiles: Vec<(Point<i32>, Vec<Direction>)>) -> u32 {
         let mut max_energized_tiles = 0;
         for starting_tile in &starting_tiles {
-            self.add_first_beam(starting_tile.0, starting_tile.1[0]);
-            let mut created_beams = HashSet::new();
-            let mut same_energized_tiles = 0;
-            let mut total_energized_tiles = 0;
-            loop {
-                let mut beans_to_be_removed = Vec::new();
-                for beam_i in 0..self.beams.len() {
-                    let mut new_bean = Vec::new();
-                    created_beams.insert(self.beams[beam_i]);
-                    let current_direction = &self.beams[beam_i].direction;
-                    let current_position = self.beams[beam_i].position;
-                    match self.get_tile_type(current_position) {
-                        ContraptionType::Empty => {}
-                        ContraptionType::MirrorRight => match current_direction {
-                            Direction::Up => {
-                                self.beams[beam_i].direction = Direction::Right;
-                            }
-                            Direction::Down => {
-                                self.beams[beam_i].direction = Direction::Left;
-                            }
-                            Direction::Left => {
-                                self.beams[beam_i].direction = Direction::Down;
-                            }
-                            Direction::Right => {
-                                self.beams[beam_i].direction = Direction::Up;
-                            }
-                        },
-                        ContraptionType::MirrorLeft => match current_direction {
-                            Direction::Up => {
-                                self.beams[beam_i].direction = Direction::Left;
-                            }
-                            Direction::Down => {
-                                self.beams[beam_i].direction = Direction::Right;
-                            }
-                            Direction::Left => {
-                                self.beams[beam_i].direction = Direction::Up;
-                            }
-                            Direction::Right => {
-                                self.beams[beam_i].direction = Direction::Down;
-                            }
-                        },
-                        ContraptionType::SplitterV => match current_direction {
-                            Direction::Up => {}
-                            Direction::Down => {}
-                            Direction::Left => {
-                                let beam = Beam {
-                                    position: current_position,
-                                    direction: Direction::Up,
-                                };
-                                if created_beams.insert(beam) {
-                                    new_bean.push(beam);
-                                }
-                                self.beams[beam_i].direction = Direction::Down;
-                            }
-                            Direction::Right => {
-                                let beam = Beam {
-                                    position: current_position,
-                                    direction: Direction::Up,
-                                };
-                                if created_beams.insert(beam) {
-                                    new_bean.push(beam);
-                                }
-                                self.beams[beam_i].direction = Direction::Down;
-                            }
-                        },
-                        ContraptionType::SplitterH => match current_direction {
-                            Direction::Up => {
-                                let beam = Beam {
-                                    position: current_position,
-                                    direction: Direction::Left,
-                                };
-                                if !created_beams.contains(&beam) {
-                                    new_bean.push(beam);
-                                }
-                                self.beams[beam_i].direction = Direction::Right;
-                            }
-                            Direction::Down => {
-                                let beam = Beam {
-                                    position: current_position,
-                                    direction: Direction::Left,
-                                };
-                                if created_beams.insert(beam) {
-                                    new_bean.push(beam);
-                                }
-                                self.beams[beam_i].direction = Direction::Right;
+            let energized_tiles = self.energize(starting_tile.0, starting_tile.1[0]);
+            self.revert_energized_tiles();
+            max_energized_tiles = max_energized_tiles.max(energized_tiles);
+        }
+        max_energized_tiles
+    }
+
+    /// Follows a beam entering at `position` until the energized tiles stop
+    /// changing, and leaves them energized.
+    fn energize(&mut self, position: Point<i32>, direction: Direction) -> u32 {
+        self.add_first_beam(position, direction);
+        let mut created_beams = HashSet::new();
+        let mut same_energized_tiles = 0;
+        let mut total_energized_tiles = 0;
+        loop {
+            let mut beans_to_be_removed = Vec::new();
+            for beam_i in 0..self.beams.len() {
+                let mut new_bean = Vec::new();
+                created_beams.insert(self.beams[beam_i]);
+                let current_direction = self.beams[beam_i].direction;
+                let current_position = self.beams[beam_i].position;
+                match self.get_tile_type(current_position) {
+                    ContraptionType::Empty => {}
+                    ContraptionType::MirrorRight => {
+                        self.beams[beam_i].direction = current_direction.reflect('/').unwrap();
+                    }
+                    ContraptionType::MirrorLeft => {
+                        self.beams[beam_i].direction = current_direction.reflect('\\').unwrap();
+                    }
+                    ContraptionType::SplitterV | ContraptionType::SplitterH => {
+                        let vertical =
+                            self.get_tile_type(current_position) == ContraptionType::SplitterV;
+                        if current_direction.is_vertical() != vertical {
+                            // Split into the beams turning left and right.
+                            let beam = Beam {
+                                position: current_position,
+                                direction: current_direction.turn_left(),
+                            };
+                            if created_beams.insert(beam) {
+                                new_bean.push(beam);
                             }
-                            Direction::Left => {}
-                            Direction::Right => {}
-                        },
+                            self.beams[beam_i].direction = current_direction.turn_right();
+                        }
                     }
+                }
 
-                    self.energize_tile(current_position);
+                self.energize_tile(current_position);
 
-                    if !new_bean.is_empty() {
-                        if let Some(pos) = new_bean[0].next_position(self.width, self.height) {
-                            self.beams.push(Beam {
-                                position: pos,
-                                direction: new_bean[0].direction,
-                            });
-                        }
-                    }
-                    if let Some(pos) = self.beams[beam_i].next_position(self.width, self.height) {
-                        self.beams[beam_i].position = pos;
-                    } else {
-                        beans_to_be_removed.push(beam_i);
+                if !new_bean.is_empty() {
+                    if let Some(pos) = new_bean[0].next_position(&self.tiles) {
+                        self.beams.push(Beam {
+                            position: pos,
+                            direction: new_bean[0].direction,
+                        });
                     }
                 }
-
-                for i in beans_to_be_removed.iter().rev() {
-                    self.beams.remove(*i);
+                if let Some(pos) = self.beams[beam_i].next_position(&self.tiles) {
+                    self.beams[beam_i].position = pos;
+                } else {
+                    beans_to_be_removed.push(beam_i);
                 }
+            }
 
-                let energized_tiles = self.calculate_energized_tiles();
+            for i in beans_to_be_removed.iter().rev() {
+                self.beams.remove(*i);
+            }
 
-                if total_energized_tiles == energized_tiles && same_energized_tiles == 10 {
-                    self.revert_energized_tiles();
-                    self.beams.clear();
-                    max_energized_tiles = max_energized_tiles.max(total_energized_tiles);
-                    break;
-                } else if total_energized_tiles == energized_tiles {
-                    same_energized_tiles += 1;
-                } else {
-                    total_energized_tiles = energized_tiles;
-                }
+            let energized_tiles = self.calculate_energized_tiles();
+
+            if total_energized_tiles == energized_tiles && same_energized_tiles == 10 {
+                self.beams.clear();
+                return total_energized_tiles;
+            } else if total_energized_tiles == energized_tiles {
+                same_energized_tiles += 1;
+            } else {
+                total_energized_tiles = energized_tiles;
             }
         }
-        max_energized_tiles
     }
 }
 
@@ -376,39 +295,26 @@ impl Contraption {
 #[cfg(test)]
 mod tests {
     const TEST_INPUT: &str = "input/day16-test.txt";
-    use aoc_2023_rust::Runner;
+    use aoc_2023_rust::{assert_snapshot, solution_tests, Runner};
 
-    use crate::solutions::day16::Day16;
+    use aoc_2023_rust::{Direction, Point};
 
-    #[test]
-    fn part1_test_works() {
-        let mut day = Day16::new();
-        day.parse(Some(TEST_INPUT));
-        let output = day.part1();
-        assert_eq!(output[0], "46")
-    }
+    use crate::solutions::day16::{Day16, INPUT};
 
-    #[test]
-    fn part1_works() {
-        let mut day = Day16::new();
-        day.parse(None);
-        let output = day.part1();
-        assert_eq!(output[0], "8901")
+    solution_tests! {
+        Day16, INPUT;
+        part1_test_works: example(TEST_INPUT), part1 => answer("46");
+        part1_works: real, part1 => answer("8901");
+        part2_test_works: example(TEST_INPUT), part2 => answer("51");
+        part2_works: real, part2 => answer("9064");
     }
 
     #[test]
-    fn part2_test_works() {
+    fn energized_snapshot() {
         let mut day = Day16::new();
         day.parse(Some(TEST_INPUT));
-        let output = day.part2();
-        assert_eq!(output[0], "51")
-    }
-
-    #[test]
-    fn part2_works() {
-        let mut day = Day16::new();
-        day.parse(None);
-        let output = day.part2();
-        assert_eq!(output[0], "9064")
+        let energized = day.contraption.energize(Point::new(0, 0), Direction::East);
+        let render = format!("{energized} energized\n{}", day.contraption);
+        assert_snapshot("day16_energized", &render);
     }
 }
@@ -1,7 +1,13 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use crate::Selector;
-use aoc_2023_rust::{run_solution, run_solution_with_part, Runner};
+use aoc_2023_rust::{
+    crosscheck, find_inputs, find_mismatch, profile, profile_report, run_solution,
+    run_solution_on_inputs, run_solution_with_part, verify_input, write_generated, Program, Runner,
+    Source,
+};
 use day01::Day01;
 use day02::Day02;
 use day03::Day03;
@@ -36,6 +42,33 @@ mod day14;
 mod day15;
 mod day16;
 
+pub fn create(day: u8) -> Option<Box<dyn Runner>> {
+    let solution: Box<dyn Runner> = match day {
+        1 => Box::new(Day01::new()),
+        2 => Box::new(Day02::new()),
+        3 => Box::new(Day03::new()),
+        4 => Box::new(Day04::new()),
+        5 => Box::new(Day05::new()),
+        6 => Box::new(Day06::new()),
+        7 => Box::new(Day07::new()),
+        8 => Box::new(Day08::new()),
+        9 => Box::new(Day09::new()),
+        10 => Box::new(Day10::new()),
+        11 => Box::new(Day11::new()),
+        12 => Box::new(Day12::new()),
+        13 => Box::new(Day13::new()),
+        14 => Box::new(Day14::new()),
+        15 => Box::new(Day15::new()),
+        16 => Box::new(Day16::new()),
+        _ => return None,
+    };
+    Some(solution)
+}
+
+pub fn days() -> Vec<u8> {
+    (1..=25).filter(|d| create(*d).is_some()).collect()
+}
+
 pub fn run(which: Selector) {
     let mut day01 = Day01::new();
     let mut day02 = Day02::new();
@@ -95,3 +128,240 @@ pub fn run(which: Selector) {
         }
     }
 }
+
+pub fn run_inputs(dir: &Path, days: &[u8]) {
+    for day in days {
+        if create(*day).is_none() {
+            eprintln!("Day {day} is not solved yet");
+            continue;
+        }
+        let inputs = find_inputs(dir, *day as usize);
+        run_solution_on_inputs(|| create(*day).unwrap(), &inputs);
+    }
+}
+
+/// Checks the alternative implementations of each day against its own parts
+/// on every input of the day found under `dir`, and on `generated` inputs of
+/// `size` from the day's generator.
+pub fn run_differential(dir: &Path, days: &[u8], generated: u64, size: usize) {
+    for day in days {
+        let Some(solution) = create(*day) else {
+            eprintln!("Day {day} is not solved yet");
+            continue;
+        };
+        if solution.alternatives().is_empty() {
+            println!("Day {day:02}: no alternative implementations");
+            continue;
+        }
+        let mut inputs = find_inputs(dir, *day as usize);
+        let generated_dir = std::env::temp_dir().join("aoc-generated");
+        match write_generated(solution.as_ref(), 0..generated, size, &generated_dir) {
+            Ok(files) => inputs.extend(files),
+            Err(e) => eprintln!("Unable to write generated inputs: {e}"),
+        }
+        match find_mismatch(|| create(*day).unwrap(), &inputs) {
+            Some(mismatch) => print!("Day {day:02}: {mismatch}"),
+            None => println!(
+                "Day {day:02}: all implementations agree on {} inputs",
+                inputs.len()
+            ),
+        }
+    }
+}
+
+/// Times a part of a day on inputs of each of `sizes` and prints how its
+/// runtime grows.
+pub fn run_profile(
+    day: u8,
+    part: u8,
+    source: &Source,
+    sizes: &[usize],
+    runs: usize,
+    max_time: Duration,
+) {
+    if create(day).is_none() {
+        eprintln!("Day {day} is not solved yet");
+        return;
+    }
+    let samples = profile(|| create(day).unwrap(), part, source, sizes, runs, max_time);
+    if samples.is_empty() {
+        eprintln!("Day {day} has no generator");
+        return;
+    }
+    println!("Day {day:02} part {part}");
+    print!("{}", profile_report(&samples));
+}
+
+/// Compares the answers of each day with those of the external `programs`
+/// on the day's own input, or on every input of the day found under `dir`.
+/// Returns whether all of them agree.
+pub fn run_crosscheck(programs: &[Program], dir: Option<&Path>, days: &[u8]) -> bool {
+    let mut all_agree = true;
+    for day in days {
+        if create(*day).is_none() {
+            eprintln!("Day {day} is not solved yet");
+            continue;
+        }
+        let inputs = match dir {
+            Some(dir) => find_inputs(dir, *day as usize),
+            None => vec![PathBuf::from(format!("input/day{day:02}.txt"))],
+        };
+        for program in programs.iter().filter(|p| p.solves(*day)) {
+            for input in &inputs {
+                let name = format!("Day {day:02} {} {}", program.name, input.display());
+                match crosscheck(|| create(*day).unwrap(), program, input) {
+                    Ok(checks) => {
+                        for check in checks {
+                            all_agree &= check.agrees();
+                            println!("{name} {check}");
+                        }
+                    }
+                    Err(e) => {
+                        all_agree = false;
+                        println!("{name} failed: {e}");
+                    }
+                }
+            }
+        }
+    }
+    all_agree
+}
+
+/// Checks the assumptions of each day on its own input, or on every input of
+/// the day found under `dir`. Returns whether all of them hold.
+pub fn run_verify_input(dir: Option<&Path>, days: &[u8]) -> bool {
+    let mut all_hold = true;
+    for day in days {
+        if create(*day).is_none() {
+            eprintln!("Day {day} is not solved yet");
+            continue;
+        }
+        let inputs: Vec<Option<String>> = match dir {
+            Some(dir) => find_inputs(dir, *day as usize)
+                .iter()
+                .map(|i| Some(i.to_string_lossy().to_string()))
+                .collect(),
+            None => vec![None],
+        };
+        for input in inputs {
+            let name = input.clone().unwrap_or_else(|| "own input".to_string());
+            match verify_input(|| create(*day).unwrap(), input.as_deref()) {
+                None => {
+                    all_hold = false;
+                    println!("Day {day:02} {name}: unable to parse");
+                }
+                Some(assumptions) if assumptions.is_empty() => {
+                    println!("Day {day:02} {name}: no assumptions");
+                }
+                Some(assumptions) => {
+                    println!("Day {day:02} {name}:");
+                    for assumption in assumptions {
+                        all_hold &= assumption.holds();
+                        println!("\t{assumption}");
+                    }
+                }
+            }
+        }
+    }
+    all_hold
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use std::fs::read;
+
+    use aoc_2023_rust::{
+        check_budget, find_inputs, find_mismatch, fuzz_solution, input_available, measure_phases,
+        regression_inputs, verify_input, write_generated, Assumption, Budget, PerfSettings,
+        PANICKING_DAYS, PERF_VAR, REGRESSIONS_DIR,
+    };
+
+    use super::{create, days};
+
+    #[test]
+    fn alternatives_agree() {
+        let dir = std::env::temp_dir().join("aoc-generated-differential");
+        for day in days() {
+            let mut inputs: Vec<PathBuf> = find_inputs("input", day as usize)
+                .into_iter()
+                .filter(|p| p.to_string_lossy().contains("-test"))
+                .collect();
+            if !create(day).unwrap().alternatives().is_empty() {
+                inputs.extend(
+                    write_generated(create(day).unwrap().as_ref(), 0..10, 10, &dir).unwrap(),
+                );
+                inputs.extend(regression_inputs(REGRESSIONS_DIR, day as usize));
+            }
+            assert_eq!(find_mismatch(|| create(day).unwrap(), &inputs), None);
+        }
+    }
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        let dir = std::env::temp_dir().join("aoc-generated-solvable");
+        for day in days() {
+            for input in write_generated(create(day).unwrap().as_ref(), 0..3, 8, &dir).unwrap() {
+                let mut solution = create(day).unwrap();
+                solution.parse(Some(&input.to_string_lossy()));
+                solution.part1();
+                solution.part2();
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_regressions() {
+        for day in days() {
+            let panicking = PANICKING_DAYS.contains(&(day as usize));
+            let inputs = regression_inputs(REGRESSIONS_DIR, day as usize);
+            let mut panics = 0;
+            for input in &inputs {
+                if !fuzz_solution(|| create(day).unwrap(), &read(input).unwrap()) {
+                    assert!(panicking, "Day {day} panics on {}", input.display());
+                    panics += 1;
+                }
+            }
+            assert!(
+                !panicking || inputs.is_empty() || panics > 0,
+                "Day {day} no longer panics on its regressions, drop it from PANICKING_DAYS"
+            );
+        }
+    }
+
+    #[test]
+    fn assumptions_hold_on_own_inputs() {
+        for day in days() {
+            if !input_available(format!("input/day{day:02}.txt")) {
+                continue;
+            }
+            let assumptions = verify_input(|| create(day).unwrap(), None).unwrap();
+            assert!(
+                assumptions.iter().all(Assumption::holds),
+                "Day {day}: {assumptions:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parts_within_budget() {
+        let Some(settings) = PerfSettings::from_env() else {
+            eprintln!("skipped: set {PERF_VAR}=1 to check the time budgets");
+            return;
+        };
+        let mut over = Vec::new();
+        for day in days() {
+            let budget = create(day).unwrap().budget();
+            if budget == Budget::default() || !input_available(format!("input/day{day:02}.txt")) {
+                continue;
+            }
+            let medians = measure_phases(|| create(day).unwrap(), None, settings.runs, &budget);
+            let (day_over, report) =
+                check_budget(day as usize, &budget, &medians, settings.tolerance);
+            print!("{report}");
+            over.extend(day_over);
+        }
+        assert!(over.is_empty(), "Over budget: {}", over.join(", "));
+    }
+}
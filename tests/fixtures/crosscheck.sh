@@ -0,0 +1,22 @@
+#!/bin/sh
+# Stand-in for an external solution program in the crosscheck tests.
+#   stdin: reads the input from standard input, answers as "Part N: answer"
+#   path:  reads the input from the path given last, one answer per line
+#   sleep: never answers in time
+case "$1" in
+stdin)
+    input=$(cat; echo x)
+    lines=$(printf '%s' "${input%x}" | wc -l)
+    bytes=$(printf '%s' "${input%x}" | wc -c)
+    echo "Solving..."
+    echo "Part 1: $((lines))"
+    echo "Part 2: $((bytes))"
+    ;;
+path)
+    echo $(($(wc -l < "$2")))
+    echo $(($(tr -d '\n' < "$2" | wc -c)))
+    ;;
+sleep)
+    sleep 5
+    ;;
+esac